@@ -0,0 +1,98 @@
+use crate::error::{ParseError, Span};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+/// Failure that may point to the part of the puzzle input that caused it.
+pub trait Diagnostic: Error {
+    fn span(&self) -> Option<Span>;
+}
+
+impl<K: Display + Debug> Diagnostic for ParseError<K> {
+    fn span(&self) -> Option<Span> {
+        Some(self.span())
+    }
+}
+
+impl Diagnostic for Infallible {
+    fn span(&self) -> Option<Span> {
+        match *self {}
+    }
+}
+
+/// Renders `diagnostic` followed by the offending line of `source` with the span underlined:
+///
+/// ```text
+/// count 'x' is not a number at line 7, column 6
+///   |
+/// 7 | move x from 1 to 3
+///   |      ^
+/// ```
+///
+/// Only the message is rendered when the diagnostic has no span inside `source`.
+pub fn render(source: &str, diagnostic: &dyn Diagnostic) -> String {
+    let message = diagnostic.to_string();
+
+    match diagnostic.span().and_then(|span| snippet(source, span)) {
+        Some(snippet) => format!("{}\n{}", message, snippet),
+        None => message,
+    }
+}
+
+fn snippet(source: &str, span: Span) -> Option<String> {
+    let line = source.lines().nth(span.start.line.checked_sub(1)?)?;
+    let line_number = span.start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    // Tabs are kept so the carets stay aligned with the line above.
+    let indent = line
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(span.start.column.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let underline = "^".repeat(span.len.max(1));
+
+    Some(format!(
+        "{gutter} |\n{line_number} | {line}\n{gutter} | {indent}{underline}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Location;
+
+    const INPUT: &str = "move 1 from 2 to 1\nmove 3 from 1 to 3\nmove x from 1 to 3";
+
+    #[test]
+    fn it_underlines_the_span_in_the_offending_line() {
+        let error = ParseError::new("count 'x' is not a number", Span::new(3, 6, 1));
+
+        assert_eq!(
+            render(INPUT, &error),
+            "count 'x' is not a number at line 3, column 6\n  |\n3 | move x from 1 to 3\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn it_underlines_every_character_of_the_span() {
+        let error = ParseError::new("unknown instruction", Span::new(2, 1, 4));
+
+        assert!(render(INPUT, &error).ends_with("2 | move 3 from 1 to 3\n  | ^^^^"));
+    }
+
+    #[test]
+    fn it_points_past_the_end_of_a_truncated_line() {
+        let error = ParseError::new("missing argument", Location::new(1, 19));
+
+        assert!(render(INPUT, &error).ends_with("1 | move 1 from 2 to 1\n  |                   ^"));
+    }
+
+    #[test]
+    fn it_renders_only_the_message_when_the_span_is_outside_the_input() {
+        let error = ParseError::new("missing line", Location::new(4, 1));
+
+        assert_eq!(render(INPUT, &error), "missing line at line 4, column 1");
+    }
+}
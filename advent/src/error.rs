@@ -0,0 +1,147 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Position of a character in the puzzle input. Both line and column start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Location {
+        Location { line, column }
+    }
+
+    /// Location of `token` inside `line_content`, which is the line number `line` of the input.
+    ///
+    /// `token` must be a slice of `line_content`, otherwise the location points to the start of
+    /// the line.
+    pub fn of(line: usize, line_content: &str, token: &str) -> Location {
+        let line_start = line_content.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        let column = token_start
+            .checked_sub(line_start)
+            .filter(|offset| offset + token.len() <= line_content.len())
+            .and_then(|offset| line_content.get(..offset))
+            .map(|before| before.chars().count() + 1)
+            .unwrap_or(1);
+
+        Location { line, column }
+    }
+}
+
+/// Range of characters in a single line of the puzzle input, starting at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Location,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, len: usize) -> Span {
+        Span {
+            start: Location::new(line, column),
+            len,
+        }
+    }
+
+    /// Span covering `token` inside `line_content`, which is the line number `line` of the input.
+    ///
+    /// Empty tokens, like the end of a truncated line, still span one character so they can be
+    /// pointed at.
+    pub fn of(line: usize, line_content: &str, token: &str) -> Span {
+        Span {
+            start: Location::of(line, line_content, token),
+            len: token.chars().count().max(1),
+        }
+    }
+}
+
+impl From<Location> for Span {
+    fn from(start: Location) -> Self {
+        Span { start, len: 1 }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Failure while parsing the puzzle input, pointing to where the input is malformed.
+///
+/// Every day defines its own `kind` describing what went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    kind: K,
+    span: Span,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(kind: K, span: impl Into<Span>) -> ParseError<K> {
+        ParseError {
+            kind,
+            span: span.into(),
+        }
+    }
+
+    pub fn kind(&self) -> &K {
+        &self.kind
+    }
+
+    pub fn location(&self) -> Location {
+        self.span.start
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Moves the error `offset` lines down, for parsers that only see a fragment of the input.
+    pub fn with_line_offset(mut self, offset: usize) -> ParseError<K> {
+        self.span.start.line += offset;
+        self
+    }
+}
+
+impl<K: Display> Display for ParseError<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.kind, self.span.start)
+    }
+}
+
+impl<K: Display + Debug> Error for ParseError<K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_locates_a_token_inside_its_line() {
+        let line = "move 1 from x to 3";
+        let token = line.split(' ').nth(3).unwrap();
+
+        assert_eq!(Location::of(4, line, token), Location::new(4, 13));
+        assert_eq!(Location::of(4, line, "x"), Location::new(4, 1));
+    }
+
+    #[test]
+    fn it_spans_the_whole_token() {
+        let line = "move 1 from xyz to 3";
+        let token = line.split(' ').nth(3).unwrap();
+
+        assert_eq!(Span::of(4, line, token), Span::new(4, 13, 3));
+        assert_eq!(Span::of(4, line, &line[line.len()..]), Span::new(4, 21, 1));
+    }
+
+    #[test]
+    fn it_displays_the_error_with_its_location() {
+        let error = ParseError::new("unknown instruction", Location::new(1, 3));
+
+        assert_eq!(error.to_string(), "unknown instruction at line 1, column 3");
+        assert_eq!(error.with_line_offset(5).location(), Location::new(6, 3));
+    }
+}
@@ -0,0 +1,77 @@
+#![deny(unused)]
+
+pub mod diagnostic;
+mod error;
+
+pub use diagnostic::Diagnostic;
+pub use error::{Location, ParseError, Span};
+use std::fmt::Display;
+
+/// Common entry point of every day of the calendar.
+///
+/// The puzzle input is parsed once with [`Solution::parse`], and both parts are answered from
+/// the parsed input, so tooling can run any day without knowing its API.
+pub trait Solution {
+    type Input;
+    type Error: Diagnostic;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Error = ParseError<String>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+            input
+                .split(',')
+                .map(|n| {
+                    n.parse().map_err(|_| {
+                        let kind = format!("'{}' is not a number", n);
+                        ParseError::new(kind, Location::of(1, input, n))
+                    })
+                })
+                .collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+            Ok(input.iter().sum())
+        }
+
+        fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+            let kind = "no numbers".to_string();
+            let location = Location::new(1, 1);
+            input
+                .iter()
+                .max()
+                .copied()
+                .ok_or(ParseError::new(kind, location))
+        }
+    }
+
+    #[test]
+    fn it_solves_both_parts_from_the_parsed_input() {
+        let input = Sum::parse("1,5,3").unwrap();
+
+        assert_eq!(Sum::part_one(&input).unwrap(), 9);
+        assert_eq!(Sum::part_two(&input).unwrap(), 5);
+        assert_eq!(
+            Sum::parse("1,a").unwrap_err().location(),
+            Location::new(1, 3)
+        );
+    }
+}
@@ -1,20 +1,290 @@
 #![allow(unused)]
 
-use itertools::Itertools;
+use advent::{Solution, Span};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
 
-pub fn count_calories(content: &str) -> u64 {
-    let items_per_elve = content.split("\n\n");
-    let calories_per_elve =
-        items_per_elve.map(|lines| lines.lines().map(|line| line.parse().unwrap_or(0)).sum());
-    calories_per_elve.max().unwrap_or(0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCalories(String),
 }
 
-pub fn count_calories_top_three(content: &str) -> u64 {
-    let items_per_elve = content.split("\n\n");
-    let calories_per_elve =
-        items_per_elve.map(|lines| lines.lines().map(|line| line.parse().unwrap_or(0)).sum::<u64>());
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::InvalidCalories(item) => {
+                write!(f, "calories '{}' is not a number", item)
+            }
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Io(std::io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Io(e) => write!(f, "unable to read the inventory: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Total calories carried by the elf at `index`, counting from 0 in the order of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: u64,
+}
+
+/// Items carried by every elf, parsed from groups of lines separated by a blank line.
+pub struct Inventory {
+    items: Vec<Vec<u64>>,
+}
+
+impl FromStr for Inventory {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::new();
+        let mut elf_items = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            match calories_from_str(i + 1, line)? {
+                Some(calories) => elf_items.push(calories),
+                None if !elf_items.is_empty() => items.push(std::mem::take(&mut elf_items)),
+                None => {}
+            }
+        }
+
+        if !elf_items.is_empty() {
+            items.push(elf_items);
+        }
+
+        Ok(Inventory { items })
+    }
+}
+
+impl Inventory {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self, elf: usize) -> Option<&[u64]> {
+        self.items.get(elf).map(Vec::as_slice)
+    }
+
+    pub fn calories(&self, elf: usize) -> Option<u64> {
+        self.items(elf).map(|items| items.iter().sum())
+    }
+
+    pub fn elves(&self) -> impl Iterator<Item = Elf> + '_ {
+        self.items.iter().enumerate().map(|(index, items)| Elf {
+            index,
+            calories: items.iter().sum(),
+        })
+    }
+
+    /// Every elf, from the one carrying the most calories to the one carrying the least.
+    ///
+    /// Elves carrying the same calories keep the order of the input.
+    pub fn ranking(&self) -> Vec<Elf> {
+        let mut ranking = self.elves().collect::<Vec<_>>();
+        ranking.sort_by_key(|elf| Reverse(elf.calories));
+        ranking
+    }
+
+    /// The `n` elves carrying the most calories, followed by any elf carrying as many calories as
+    /// the last of them.
+    pub fn top_n(&self, n: usize) -> Vec<Elf> {
+        let mut ranking = self.ranking();
+        let Some(last) = n.checked_sub(1).and_then(|i| ranking.get(i)).copied() else {
+            ranking.truncate(n);
+            return ranking;
+        };
+
+        ranking.retain(|elf| elf.calories >= last.calories);
+        ranking
+    }
+
+    /// Sum of the calories carried by the `n` elves carrying the most.
+    pub fn top_calories(&self, n: usize) -> u64 {
+        self.ranking().iter().take(n).map(|elf| elf.calories).sum()
+    }
+
+    /// Position of the elf in the ranking, starting at 1. Elves carrying the same calories share
+    /// the same rank.
+    pub fn rank(&self, elf: usize) -> Option<usize> {
+        let calories = self.calories(elf)?;
+        let carrying_more = self.elves().filter(|e| e.calories > calories).count();
+        Some(carrying_more + 1)
+    }
+
+    /// Fewest calories that at least `percentile` percent of the elves carry no more than, using
+    /// the nearest-rank method. `None` if the inventory is empty or `percentile` is not between 0
+    /// and 100.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        if self.is_empty() || !(0.0..=100.0).contains(&percentile) {
+            return None;
+        }
+
+        let mut calories = self.elves().map(|elf| elf.calories).collect::<Vec<_>>();
+        calories.sort_unstable();
+
+        let rank = (percentile / 100.0 * calories.len() as f64).ceil() as usize;
+        Some(calories[rank.max(1) - 1])
+    }
+}
+
+/// Keeps the `k` elves carrying the most calories among the ones pushed so far, forgetting about
+/// the rest.
+pub struct TopCalories {
+    k: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl TopCalories {
+    pub fn new(k: usize) -> TopCalories {
+        TopCalories {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: Elf) {
+        // The heap top is the elf to drop first: the one carrying the least calories and, among
+        // ties, the last one in the input, so the result matches `Inventory::top_n`.
+        self.heap.push(Reverse((elf.calories, Reverse(elf.index))));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// The kept elves, from the one carrying the most calories to the one carrying the least.
+    pub fn into_elves(self) -> Vec<Elf> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+            .collect()
+    }
+}
 
-    calories_per_elve.sorted().rev().take(3).sum()
+/// The `k` elves carrying the most calories, reading the inventory line by line from `reader`.
+///
+/// Only the current line and the `k` best elves are kept in memory, so the inventory can be
+/// arbitrarily large.
+pub fn top_calories_from_reader<R: BufRead>(mut reader: R, k: usize) -> Result<Vec<Elf>, Error> {
+    let mut top = TopCalories::new(k);
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut index = 0;
+    let mut calories = None;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        match calories_from_str(line_number, &line)? {
+            Some(item) => *calories.get_or_insert(0) += item,
+            None => {
+                if let Some(calories) = calories.take() {
+                    top.push(Elf { index, calories });
+                    index += 1;
+                }
+            }
+        }
+    }
+    if let Some(calories) = calories {
+        top.push(Elf { index, calories });
+    }
+
+    Ok(top.into_elves())
+}
+
+/// Calories of the item in the line number `line_number`, or `None` if the line separates two
+/// elves.
+fn calories_from_str(line_number: usize, line: &str) -> Result<Option<u64>, ParseError> {
+    let item = line.trim();
+    if item.is_empty() {
+        return Ok(None);
+    }
+
+    item.parse().map(Some).map_err(|_| {
+        let kind = ParseErrorKind::InvalidCalories(item.to_string());
+        ParseError::new(kind, Span::of(line_number, line, item))
+    })
+}
+
+pub fn count_calories(content: &str) -> Result<u64, ParseError> {
+    count_calories_top(content, 1)
+}
+
+pub fn count_calories_top_three(content: &str) -> Result<u64, ParseError> {
+    count_calories_top(content, 3)
+}
+
+pub fn count_calories_top(content: &str, n: usize) -> Result<u64, ParseError> {
+    let inventory = Inventory::from_str(content)?;
+    Ok(inventory.top_calories(n))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Inventory;
+    type Error = ParseError;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Inventory::from_str(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(input.top_calories(1))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(input.top_calories(3))
+    }
 }
 
 #[cfg(test)]
@@ -23,18 +293,132 @@ mod tests {
 
     #[test]
     fn it_counts_the_calories() {
-        let result = count_calories(calories());
+        let result = count_calories(calories()).unwrap();
 
         assert_eq!(result, 24000);
     }
 
     #[test]
     fn it_counts_the_top_three_carriers_sum() {
-        let result = count_calories_top_three(calories());
+        let result = count_calories_top_three(calories()).unwrap();
 
         assert_eq!(result, 45000);
     }
 
+    #[test]
+    fn it_reports_what_every_elf_carries() {
+        let inventory = Inventory::from_str(calories()).unwrap();
+
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.items(2), Some([5000, 6000].as_slice()));
+        assert_eq!(inventory.calories(3), Some(24000));
+        assert_eq!(inventory.calories(5), None);
+    }
+
+    #[test]
+    fn it_ranks_the_elves_by_calories() {
+        let inventory = Inventory::from_str(calories()).unwrap();
+
+        let top = inventory.top_n(2);
+
+        assert_eq!(
+            top,
+            vec![
+                Elf {
+                    index: 3,
+                    calories: 24000
+                },
+                Elf {
+                    index: 2,
+                    calories: 11000
+                }
+            ]
+        );
+        assert_eq!(inventory.rank(3), Some(1));
+        assert_eq!(inventory.rank(1), Some(5));
+        assert_eq!(inventory.rank(7), None);
+    }
+
+    #[test]
+    fn it_keeps_the_elves_tied_with_the_last_of_the_top() {
+        let inventory = Inventory::from_str("300\n\n100\n\n200\n\n100\n50\n50").unwrap();
+
+        let top = inventory.top_n(2);
+
+        assert_eq!(
+            top.iter().map(|elf| elf.index).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert_eq!(inventory.rank(3), Some(2));
+        assert_eq!(inventory.rank(1), Some(4));
+        assert_eq!(inventory.top_calories(2), 500);
+    }
+
+    #[test]
+    fn it_computes_percentiles() {
+        let inventory = Inventory::from_str(calories()).unwrap();
+
+        assert_eq!(inventory.percentile(0.0), Some(4000));
+        assert_eq!(inventory.percentile(50.0), Some(10000));
+        assert_eq!(inventory.percentile(100.0), Some(24000));
+        assert_eq!(inventory.percentile(101.0), None);
+    }
+
+    #[test]
+    fn it_rejects_malformed_lines() {
+        let input = calories().replace("5000", "5OOO");
+
+        let error = Inventory::from_str(&input).err().unwrap();
+
+        assert_eq!(
+            error,
+            ParseError::new(
+                ParseErrorKind::InvalidCalories("5OOO".to_string()),
+                Span::new(7, 1, 4)
+            )
+        );
+    }
+
+    #[test]
+    fn it_streams_the_top_carriers_from_a_reader() {
+        let top = top_calories_from_reader(calories().as_bytes(), 3).unwrap();
+
+        assert_eq!(
+            top.iter()
+                .map(|elf| (elf.index, elf.calories))
+                .collect::<Vec<_>>(),
+            vec![(3, 24000), (2, 11000), (4, 10000)]
+        );
+        assert!(top_calories_from_reader(calories().as_bytes(), 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn it_streams_the_same_top_as_the_inventory() {
+        let input = "300\n\n100\n\n200\n\n100\n50\n50\n\n\n200\n";
+        let inventory = Inventory::from_str(input).unwrap();
+
+        for k in 0..6 {
+            let streamed = top_calories_from_reader(input.as_bytes(), k).unwrap();
+            let ranking = inventory.ranking();
+
+            assert_eq!(streamed, ranking[..k.min(ranking.len())]);
+        }
+    }
+
+    #[test]
+    fn it_reports_malformed_lines_while_streaming() {
+        let input = calories().replace("8000", "8k");
+
+        let error = top_calories_from_reader(input.as_bytes(), 3).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "calories '8k' is not a number at line 11, column 1"
+        );
+    }
+
     fn calories() -> &'static str {
         "1000
 2000
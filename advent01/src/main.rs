@@ -1,8 +1,10 @@
-use advent01::{count_calories, count_calories_top_three};
+use advent01::Inventory;
+use std::str::FromStr;
 
 fn main() {
-    let calories = count_calories(input());
-    let calories_top_3 = count_calories_top_three(input());
+    let inventory = Inventory::from_str(input()).unwrap();
+    let calories = inventory.top_calories(1);
+    let calories_top_3 = inventory.top_calories(3);
 
     println!("Total calories: {}", calories);
     println!("Total calories of top 3: {}", calories_top_3);
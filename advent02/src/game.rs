@@ -0,0 +1,139 @@
+use crate::{guide_columns_from_str, parse_lines, Outcome, ParseError, ParseErrorKind, Rules};
+
+/// Guide played with any [`Rules`].
+///
+/// Shapes are written with their name in the rules, or with the letters of the puzzle for the
+/// first three shapes: A, B and C in the enemy column, X, Y and Z in mine.
+#[derive(Debug)]
+pub struct Game<'r> {
+    rules: &'r Rules,
+    rounds: Vec<(usize, usize)>,
+}
+
+impl<'r> Game<'r> {
+    /// Game where the second column of the guide is the shape I play.
+    pub fn new(input: &str, rules: &'r Rules) -> Result<Game<'r>, ParseError> {
+        Self::from_guide(input, rules, |_, word| shape(rules, word, ["X", "Y", "Z"]))
+    }
+
+    /// Game where the second column of the guide is how the round needs to end: X to lose, Y to
+    /// draw and Z to win.
+    pub fn new_with_end_result(input: &str, rules: &'r Rules) -> Result<Game<'r>, ParseError> {
+        Self::from_guide(input, rules, |&enemy, word| {
+            let outcome = match word {
+                "X" => Outcome::Lost,
+                "Y" => Outcome::Draw,
+                "Z" => Outcome::Won,
+                _ => return Err(ParseErrorKind::InvalidColumn(word.to_string())),
+            };
+            rules
+                .shape_for_outcome(enemy, outcome)
+                .ok_or(ParseErrorKind::ImpossibleOutcome(outcome))
+        })
+    }
+
+    fn from_guide(
+        input: &str,
+        rules: &'r Rules,
+        myself: impl Fn(&usize, &str) -> Result<usize, ParseErrorKind>,
+    ) -> Result<Game<'r>, ParseError> {
+        let rounds = parse_lines(input, |line| {
+            guide_columns_from_str(line, |word| shape(rules, word, ["A", "B", "C"]), &myself)
+        })?;
+
+        Ok(Game { rules, rounds })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Names of the enemy shape and of mine in every round.
+    pub fn shapes(&self) -> impl Iterator<Item = (&str, &str)> {
+        let shapes = self.rules.shapes();
+        self.rounds
+            .iter()
+            .map(|&(enemy, myself)| (shapes[enemy].name(), shapes[myself].name()))
+    }
+
+    pub fn outcomes(&self) -> impl Iterator<Item = Outcome> + '_ {
+        self.rounds
+            .iter()
+            .map(|&(enemy, myself)| self.rules.outcome(enemy, myself))
+    }
+
+    pub fn score(&self) -> u32 {
+        self.rounds
+            .iter()
+            .map(|&(enemy, myself)| self.rules.score(enemy, myself))
+            .sum()
+    }
+}
+
+/// Shape written as `word`, either one of the `letters` of the first three shapes or a name.
+fn shape(rules: &Rules, word: &str, letters: [&str; 3]) -> Result<usize, ParseErrorKind> {
+    letters
+        .iter()
+        .position(|&letter| letter == word)
+        .filter(|&shape| shape < rules.shapes().len())
+        .or_else(|| rules.shape(word))
+        .ok_or_else(|| ParseErrorKind::UnknownShape(word.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::Span;
+
+    fn input() -> &'static str {
+        "A Spock
+Lizard X
+Spock Y
+C Lizard"
+    }
+
+    #[test]
+    fn it_plays_the_guide_with_any_rules() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+
+        let game = Game::new(input(), &rules).unwrap();
+
+        assert_eq!(game.rounds(), 4);
+        assert_eq!(
+            game.outcomes().collect::<Vec<_>>(),
+            vec![Outcome::Won, Outcome::Won, Outcome::Won, Outcome::Lost]
+        );
+        assert_eq!(game.score(), 10 + 7 + 8 + 5);
+    }
+
+    #[test]
+    fn it_picks_the_shape_for_the_end_result_from_the_rules() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+
+        let game = Game::new_with_end_result("Lizard X\nSpock Y\nA Z", &rules).unwrap();
+
+        assert_eq!(
+            game.shapes().collect::<Vec<_>>(),
+            vec![("Lizard", "Paper"), ("Spock", "Spock"), ("Rock", "Paper")]
+        );
+        assert_eq!(game.score(), 2 + 7 + 8);
+    }
+
+    #[test]
+    fn it_rejects_shapes_and_outcomes_out_of_the_rules() {
+        let rules = Rules::cyclic(&["Rock"]).unwrap();
+
+        let unknown = Game::new("A Rock\nB Rock", &rules).unwrap_err();
+        let impossible = Game::new_with_end_result("Rock Z", &rules).unwrap_err();
+
+        assert_eq!(
+            unknown.kind(),
+            &ParseErrorKind::UnknownShape("B".to_string())
+        );
+        assert_eq!(unknown.span(), Span::new(2, 1, 1));
+        assert_eq!(
+            impossible.kind(),
+            &ParseErrorKind::ImpossibleOutcome(Outcome::Won)
+        );
+    }
+}
@@ -1,13 +1,70 @@
 #![deny(unused)]
 
+mod game;
+mod rules;
+mod solver;
+mod tournament;
+
+use advent::{Solution, Span};
+pub use game::Game;
+pub use rules::{Outcome, Rules, RulesError, Shape, MAX_SHAPES};
+pub use solver::{Evaluation, Interpretation, Solver};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+pub use tournament::{
+    play_match, AlwaysRock, Cycle, FrequencyCounter, MatchResult, MirrorLastMove, Player,
+    SeededRandom, Standing, Tournament,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidEnemyShape(String),
+    MissingColumn,
+    InvalidColumn(String),
+    TooManyColumns,
+    UnknownShape(String),
+    ImpossibleOutcome(Outcome),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::InvalidEnemyShape(shape) => {
+                write!(f, "enemy shape '{}' is not A, B or C", shape)
+            }
+            ParseErrorKind::MissingColumn => write!(f, "missing X, Y or Z column"),
+            ParseErrorKind::InvalidColumn(column) => {
+                write!(f, "column '{}' is not X, Y or Z", column)
+            }
+            ParseErrorKind::TooManyColumns => write!(f, "unexpected column after X, Y or Z"),
+            ParseErrorKind::UnknownShape(shape) => {
+                write!(f, "'{}' is not a shape of the game", shape)
+            }
+            ParseErrorKind::ImpossibleOutcome(outcome) => {
+                let verb = match outcome {
+                    Outcome::Lost => "loses",
+                    Outcome::Draw => "draws",
+                    Outcome::Won => "wins",
+                };
+                write!(f, "no shape {} against the enemy shape", verb)
+            }
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+/// Shape played in a round, encoded in the guide as:
+///
 /// A -> Rock
 /// B -> Paper
 /// C -> Scissors
 /// X -> Rock
 /// Y -> Paper
 /// Z -> Scissors
-#[derive(Debug, PartialEq)]
-enum HandShape {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandShape {
     Rock,
     Paper,
     Scissors,
@@ -23,44 +80,112 @@ impl HandShape {
         }
     }
 
-    fn shape_to_lose_against(&self) -> HandShape {
+    /// Letter encoding the shape in the enemy column of the guide.
+    pub fn enemy_char(&self) -> char {
         match self {
-            HandShape::Rock => HandShape::Scissors,
-            HandShape::Paper => HandShape::Rock,
-            HandShape::Scissors => HandShape::Paper,
+            HandShape::Rock => 'A',
+            HandShape::Paper => 'B',
+            HandShape::Scissors => 'C',
         }
     }
 
-    fn shape_to_win_against(&self) -> HandShape {
+    /// Letter encoding the shape in my column of the guide.
+    pub fn my_char(&self) -> char {
         match self {
-            HandShape::Rock => HandShape::Paper,
-            HandShape::Paper => HandShape::Scissors,
-            HandShape::Scissors => HandShape::Rock,
+            HandShape::Rock => 'X',
+            HandShape::Paper => 'Y',
+            HandShape::Scissors => 'Z',
         }
     }
 
-    fn shape_to_draw_against(&self) -> HandShape {
+    /// Score of playing the shape, regardless of the outcome of the round.
+    pub fn score(&self) -> u32 {
+        classic_rules().shapes()[self.index()].score()
+    }
+
+    /// Position of the shape in [`Rules::classic`].
+    pub(crate) fn index(&self) -> usize {
         match self {
-            HandShape::Rock => HandShape::Rock,
-            HandShape::Paper => HandShape::Paper,
-            HandShape::Scissors => HandShape::Scissors,
+            HandShape::Rock => 0,
+            HandShape::Paper => 1,
+            HandShape::Scissors => 2,
         }
     }
+
+    pub(crate) fn from_index(index: usize) -> Option<HandShape> {
+        [HandShape::Rock, HandShape::Paper, HandShape::Scissors]
+            .get(index)
+            .copied()
+    }
+
+    /// Shape that gets `outcome` when played against this one, as told by [`Rules::classic`].
+    fn shape_for_outcome(&self, outcome: Outcome) -> HandShape {
+        classic_rules()
+            .shape_for_outcome(self.index(), outcome)
+            .and_then(HandShape::from_index)
+            .expect("every outcome is possible in the classic game")
+    }
+}
+
+impl Display for HandShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", classic_rules().shapes()[self.index()].name())
+    }
+}
+
+pub(crate) fn classic_rules() -> &'static Rules {
+    static CLASSIC: OnceLock<Rules> = OnceLock::new();
+    CLASSIC.get_or_init(Rules::classic)
 }
 
-#[derive(Debug, PartialEq)]
-struct Round {
+/// Round of the guide: the shape the enemy plays and the shape I play.
+///
+/// Rounds are written as in the guide, where the second column is the shape I play:
+///
+/// ```
+/// use advent02::{HandShape, Round};
+///
+/// let round = "A Y".parse::<Round>().unwrap();
+///
+/// assert_eq!(round, Round::new(HandShape::Rock, HandShape::Paper));
+/// assert_eq!(round.to_string(), "A Y");
+/// assert!(round.won());
+/// assert_eq!((round.shape_score(), round.outcome_score()), (2, 6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
     enemy: HandShape,
     myself: HandShape,
 }
 
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (enemy, column) = guide_line_from_str(line)?;
+        let myself = [HandShape::Rock, HandShape::Paper, HandShape::Scissors][column];
+
+        Ok(Round { enemy, myself })
+    }
+}
+
+impl Display for Round {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.enemy.enemy_char(), self.myself.my_char())
+    }
+}
+
 impl Round {
-    #[cfg(test)]
-    pub fn new(left: HandShape, right: HandShape) -> Round {
-        Round {
-            enemy: left,
-            myself: right,
-        }
+    pub fn new(enemy: HandShape, myself: HandShape) -> Round {
+        Round { enemy, myself }
+    }
+
+    pub fn enemy(&self) -> HandShape {
+        self.enemy
+    }
+
+    pub fn myself(&self) -> HandShape {
+        self.myself
     }
 
     pub fn from_line(line: &str) -> Option<Round> {
@@ -82,9 +207,9 @@ impl Round {
         let left = chars.next().and_then(HandShape::from_char)?;
         chars.next(); // skip space
         let right = match chars.next() {
-            Some('X') => left.shape_to_lose_against(),
-            Some('Y') => left.shape_to_draw_against(),
-            Some('Z') => left.shape_to_win_against(),
+            Some('X') => left.shape_for_outcome(Outcome::Lost),
+            Some('Y') => left.shape_for_outcome(Outcome::Draw),
+            Some('Z') => left.shape_for_outcome(Outcome::Won),
             _ => return None,
         };
 
@@ -94,76 +219,182 @@ impl Round {
         })
     }
 
-    pub fn score(&self) -> u32 {
-        self.shape_score() + self.outcome_score()
+    /// Round where the second column of the guide line is how the round needs to end: X to
+    /// lose, Y to draw and Z to win.
+    fn from_str_with_end_result(line: &str) -> Result<Round, ParseError> {
+        let (enemy, column) = guide_line_from_str(line)?;
+        let outcome = [Outcome::Lost, Outcome::Draw, Outcome::Won][column];
+        let myself = enemy.shape_for_outcome(outcome);
+
+        Ok(Round { enemy, myself })
     }
 
-    fn won(&self) -> bool {
-        matches!(
-            (&self.enemy, &self.myself),
-            (HandShape::Rock, HandShape::Paper)
-                | (HandShape::Paper, HandShape::Scissors)
-                | (HandShape::Scissors, HandShape::Rock)
-        )
+    /// Total score of the round, which is [`Round::shape_score`] plus [`Round::outcome_score`].
+    pub fn score(&self) -> u32 {
+        classic_rules().score(self.enemy.index(), self.myself.index())
     }
 
-    fn draw(&self) -> bool {
-        self.enemy == self.myself
+    pub fn shape_score(&self) -> u32 {
+        self.myself.score()
     }
 
-    fn lost(&self) -> bool {
-        !self.won() && !self.draw()
+    pub fn outcome_score(&self) -> u32 {
+        self.outcome().score()
     }
 
-    fn shape_score(&self) -> u32 {
-        match &self.myself {
-            HandShape::Rock => 1,
-            HandShape::Paper => 2,
-            HandShape::Scissors => 3,
+    /// Score of the round when played with `rules`, where rock, paper and scissors are the first
+    /// three shapes of `rules`, so it fails if `rules` has less than three shapes.
+    pub fn score_with(&self, rules: &Rules) -> Result<u32, RulesError> {
+        let shapes = rules.shapes().len();
+        if let Some(missing) = [self.enemy, self.myself]
+            .iter()
+            .map(HandShape::index)
+            .find(|&shape| shape >= shapes)
+        {
+            return Err(RulesError::UnknownShape(missing));
         }
+
+        Ok(rules.score(self.enemy.index(), self.myself.index()))
     }
 
-    fn outcome_score(&self) -> u32 {
-        if self.lost() {
-            0
-        } else if self.draw() {
-            3
-        } else {
-            6
-        }
+    /// Outcome of the round for me.
+    pub fn outcome(&self) -> Outcome {
+        classic_rules().outcome(self.enemy.index(), self.myself.index())
+    }
+
+    pub fn won(&self) -> bool {
+        self.outcome() == Outcome::Won
+    }
+
+    pub fn draw(&self) -> bool {
+        self.outcome() == Outcome::Draw
+    }
+
+    pub fn lost(&self) -> bool {
+        self.outcome() == Outcome::Lost
+    }
+}
+
+/// Every line of the guide read by `line_from_str`, skipping blank lines, with errors at their line
+/// of the guide.
+pub(crate) fn parse_lines<T>(
+    input: &str,
+    line_from_str: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| line_from_str(line).map_err(|e| e.with_line_offset(i)))
+        .collect()
+}
+
+/// The enemy shape and the column (0 for X, 1 for Y, 2 for Z) of a line of the guide.
+pub(crate) fn guide_line_from_str(line: &str) -> Result<(HandShape, usize), ParseError> {
+    guide_columns_from_str(
+        line,
+        |word| match word {
+            "A" => Ok(HandShape::Rock),
+            "B" => Ok(HandShape::Paper),
+            "C" => Ok(HandShape::Scissors),
+            _ => Err(ParseErrorKind::InvalidEnemyShape(word.to_string())),
+        },
+        |_, word| {
+            ["X", "Y", "Z"]
+                .iter()
+                .position(|&column| column == word)
+                .ok_or_else(|| ParseErrorKind::InvalidColumn(word.to_string()))
+        },
+    )
+}
+
+/// The two columns of a line of the guide, read by `enemy` and by `column`, which also gets what
+/// `enemy` read.
+pub(crate) fn guide_columns_from_str<E, C>(
+    line: &str,
+    enemy: impl FnOnce(&str) -> Result<E, ParseErrorKind>,
+    column: impl FnOnce(&E, &str) -> Result<C, ParseErrorKind>,
+) -> Result<(E, C), ParseError> {
+    let mut words = line.split_whitespace();
+
+    let enemy_word = words.next().unwrap_or(&line[line.len()..]);
+    let enemy =
+        enemy(enemy_word).map_err(|kind| ParseError::new(kind, Span::of(1, line, enemy_word)))?;
+
+    let Some(column_word) = words.next() else {
+        let location = Span::of(1, line, &line[line.len()..]);
+        return Err(ParseError::new(ParseErrorKind::MissingColumn, location));
+    };
+    let column = column(&enemy, column_word)
+        .map_err(|kind| ParseError::new(kind, Span::of(1, line, column_word)))?;
+
+    if let Some(extra) = words.next() {
+        let kind = ParseErrorKind::TooManyColumns;
+        return Err(ParseError::new(kind, Span::of(1, line, extra)));
     }
+
+    Ok((enemy, column))
 }
 
+#[derive(Debug)]
 pub struct Strategy {
     rounds: Vec<Round>,
 }
 
 impl Strategy {
-    pub fn new(input: &str) -> Self {
-        let rounds = input
-            .lines()
-            .filter_map(Round::from_line)
-            .collect::<Vec<Round>>();
-
-        Strategy { rounds }
+    /// Strategy where the second column of the guide is the shape I play.
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let rounds = parse_lines(input, Round::from_str)?;
+        Ok(Strategy { rounds })
     }
 
-    pub fn new_with_end_result(input: &str) -> Self {
-        let rounds = input
-            .lines()
-            .filter_map(Round::from_line_with_end_result)
-            .collect::<Vec<Round>>();
-
-        Strategy { rounds }
+    /// Strategy where the second column of the guide is how the round needs to end.
+    pub fn new_with_end_result(input: &str) -> Result<Self, ParseError> {
+        let rounds = parse_lines(input, Round::from_str_with_end_result)?;
+        Ok(Strategy { rounds })
     }
 
     pub fn rounds(&self) -> usize {
         self.rounds.len()
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Round> {
+        self.rounds.iter()
+    }
+
     pub fn score(&self) -> u32 {
         self.rounds.iter().map(Round::score).sum()
     }
+
+    /// Total score when the rounds are played with `rules`, see [`Round::score_with`].
+    pub fn score_with(&self, rules: &Rules) -> Result<u32, RulesError> {
+        self.rounds
+            .iter()
+            .map(|round| round.score_with(rules))
+            .sum()
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    /// The guide read with both meanings of its second column.
+    type Input = (Strategy, Strategy);
+    type Error = ParseError;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok((Strategy::new(input)?, Strategy::new_with_end_result(input)?))
+    }
+
+    fn part_one((strategy, _): &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(strategy.score())
+    }
+
+    fn part_two((_, strategy): &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(strategy.score())
+    }
 }
 
 #[cfg(test)]
@@ -172,11 +403,11 @@ mod tests {
 
     #[test]
     fn it_retrieves_the_strategy() {
-        let strategy = Strategy::new(input());
+        let strategy = Strategy::new(input()).unwrap();
 
         assert_eq!(strategy.rounds(), 3);
         assert_eq!(
-            strategy.rounds.get(0),
+            strategy.rounds.first(),
             Some(&Round::new(HandShape::Rock, HandShape::Paper))
         );
         assert_eq!(strategy.score(), 15);
@@ -233,7 +464,7 @@ mod tests {
 
     #[test]
     fn it_returns_the_score_with_strategy_ending() {
-        let strategy = Strategy::new_with_end_result(input());
+        let strategy = Strategy::new_with_end_result(input()).unwrap();
 
         let rounds = &strategy.rounds;
         assert_eq!(rounds[0].enemy, HandShape::Rock);
@@ -249,6 +480,79 @@ mod tests {
         assert_eq!(strategy.score(), 12);
     }
 
+    #[test]
+    fn it_scores_the_strategy_with_other_rules() {
+        let strategy = Strategy::new(input()).unwrap();
+        let shapes = vec![
+            Shape::new("Rock", 10),
+            Shape::new("Paper", 20),
+            Shape::new("Scissors", 30),
+        ];
+        let expensive = Rules::new(shapes, &[(0, 2), (1, 0), (2, 1)]).unwrap();
+
+        assert_eq!(strategy.score_with(&Rules::classic()), Ok(strategy.score()));
+        assert_eq!(strategy.score_with(&expensive), Ok(26 + 10 + 33));
+        assert_eq!(
+            strategy.score_with(&Rules::cyclic(&["Rock"]).unwrap()),
+            Err(RulesError::UnknownShape(1))
+        );
+    }
+
+    #[test]
+    fn it_round_trips_a_round_through_its_text() {
+        let lines = [
+            "A X", "A Y", "A Z", "B X", "B Y", "B Z", "C X", "C Y", "C Z",
+        ];
+
+        for line in lines {
+            let round = Round::from_str(line).unwrap();
+
+            assert_eq!(round.to_string(), line);
+            assert_eq!(Round::from_line(line), Some(round));
+        }
+        assert_eq!(
+            Round::from_str("A W").unwrap_err().kind(),
+            &ParseErrorKind::InvalidColumn("W".to_string())
+        );
+    }
+
+    #[test]
+    fn it_breaks_down_the_score_of_every_round() {
+        let strategy = Strategy::new_with_end_result(input()).unwrap();
+
+        let breakdown = strategy
+            .iter()
+            .map(|round| (round.shape_score(), round.outcome_score()))
+            .collect::<Vec<_>>();
+        let outcomes = strategy
+            .iter()
+            .map(|round| (round.lost(), round.draw(), round.won()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(breakdown, vec![(1, 3), (1, 0), (1, 6)]);
+        assert_eq!(
+            outcomes,
+            vec![
+                (false, true, false),
+                (true, false, false),
+                (false, false, true)
+            ]
+        );
+        assert_eq!(HandShape::Scissors.to_string(), "Scissors");
+    }
+
+    #[test]
+    fn it_reports_the_malformed_line_of_the_guide() {
+        let error = Day02::parse("A Y\n\nB W\nC Z").unwrap_err();
+
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::InvalidColumn("W".to_string())
+        );
+        assert_eq!(error.span(), Span::new(3, 3, 1));
+        assert!(Strategy::new_with_end_result("A Y\nD X").is_err());
+    }
+
     fn input() -> &'static str {
         "A Y
 B X
@@ -1,8 +1,8 @@
 use advent02::Strategy;
 
 fn main() {
-    let strategy = Strategy::new(input());
-    let strategy_with_end_result = Strategy::new_with_end_result(input());
+    let strategy = Strategy::new(input()).unwrap();
+    let strategy_with_end_result = Strategy::new_with_end_result(input()).unwrap();
     let score = strategy.score();
 
     println!("Score: {}", score);
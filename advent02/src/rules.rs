@@ -0,0 +1,263 @@
+use std::fmt::{Display, Formatter};
+
+/// Maximum number of shapes in a game, so the shapes beaten by each one fit in a `u64`.
+pub const MAX_SHAPES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lost,
+    Draw,
+    Won,
+}
+
+impl Outcome {
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Lost => 0,
+            Outcome::Draw => 3,
+            Outcome::Won => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    name: String,
+    score: u32,
+}
+
+impl Shape {
+    pub fn new(name: &str, score: u32) -> Shape {
+        Shape {
+            name: name.to_string(),
+            score,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    NoShapes,
+    TooManyShapes(usize),
+    EvenShapeCount(usize),
+    UnknownShape(usize),
+    BeatsItself(usize),
+    BeatEachOther(usize, usize),
+}
+
+impl Display for RulesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulesError::NoShapes => write!(f, "the game has no shapes"),
+            RulesError::TooManyShapes(count) => write!(
+                f,
+                "the game has {} shapes, but at most {} are supported",
+                count, MAX_SHAPES
+            ),
+            RulesError::EvenShapeCount(count) => write!(
+                f,
+                "a cyclic game needs an odd number of shapes, but {} were given",
+                count
+            ),
+            RulesError::UnknownShape(shape) => write!(f, "shape {} does not exist", shape),
+            RulesError::BeatsItself(shape) => write!(f, "shape {} beats itself", shape),
+            RulesError::BeatEachOther(a, b) => write!(f, "shapes {} and {} beat each other", a, b),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+/// Shapes of a game and which one beats which. Shapes are identified by their position.
+///
+/// Two different shapes where none beats the other end in a draw, like two equal shapes do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<Shape>,
+    beaten_by: Vec<u64>,
+}
+
+impl Rules {
+    /// Game where every `(winner, loser)` pair in `wins` tells that `winner` beats `loser`.
+    pub fn new(shapes: Vec<Shape>, wins: &[(usize, usize)]) -> Result<Rules, RulesError> {
+        if shapes.is_empty() {
+            return Err(RulesError::NoShapes);
+        }
+        if shapes.len() > MAX_SHAPES {
+            return Err(RulesError::TooManyShapes(shapes.len()));
+        }
+
+        let mut beaten_by = vec![0u64; shapes.len()];
+        for &(winner, loser) in wins {
+            if let Some(&unknown) = [winner, loser].iter().find(|&&s| s >= shapes.len()) {
+                return Err(RulesError::UnknownShape(unknown));
+            }
+            if winner == loser {
+                return Err(RulesError::BeatsItself(winner));
+            }
+            if beaten_by[loser] & (1 << winner) != 0 {
+                return Err(RulesError::BeatEachOther(winner, loser));
+            }
+            beaten_by[winner] |= 1 << loser;
+        }
+
+        Ok(Rules { shapes, beaten_by })
+    }
+
+    /// Game where every shape beats the ones an odd number of positions before it, wrapping
+    /// around, so every shape beats and loses against the same number of shapes. Shapes score
+    /// their position, starting at 1.
+    pub fn cyclic(names: &[&str]) -> Result<Rules, RulesError> {
+        let count = names.len();
+        if count.is_multiple_of(2) && count > 0 {
+            return Err(RulesError::EvenShapeCount(count));
+        }
+
+        let shapes = names
+            .iter()
+            .zip(1..)
+            .map(|(name, score)| Shape::new(name, score))
+            .collect();
+        let wins = (0..count)
+            .flat_map(|winner| {
+                (1..count)
+                    .step_by(2)
+                    .map(move |distance| (winner, (winner + count - distance) % count))
+            })
+            .collect::<Vec<_>>();
+
+        Rules::new(shapes, &wins)
+    }
+
+    /// Rock, paper, scissors, as played in the puzzle.
+    pub fn classic() -> Rules {
+        Rules::cyclic(&["Rock", "Paper", "Scissors"]).expect("odd number of shapes")
+    }
+
+    /// Rock, paper, scissors, Spock, lizard. The first three shapes play like in the classic game.
+    pub fn rock_paper_scissors_lizard_spock() -> Rules {
+        Rules::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"])
+            .expect("odd number of shapes")
+    }
+
+    pub fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    pub fn shape(&self, name: &str) -> Option<usize> {
+        self.shapes.iter().position(|shape| shape.name == name)
+    }
+
+    pub fn beats(&self, winner: usize, loser: usize) -> bool {
+        loser < self.shapes.len()
+            && self
+                .beaten_by
+                .get(winner)
+                .is_some_and(|beaten| beaten & (1 << loser) != 0)
+    }
+
+    pub fn outcome(&self, enemy: usize, myself: usize) -> Outcome {
+        if self.beats(myself, enemy) {
+            Outcome::Won
+        } else if self.beats(enemy, myself) {
+            Outcome::Lost
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Score of playing `myself` against `enemy`: the score of my shape plus the outcome's one.
+    pub fn score(&self, enemy: usize, myself: usize) -> u32 {
+        self.shapes[myself].score + self.outcome(enemy, myself).score()
+    }
+
+    /// First shape that gets `outcome` when played against `enemy`, if any.
+    pub fn shape_for_outcome(&self, enemy: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.shapes.len()).find(|&myself| self.outcome(enemy, myself) == outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_plays_the_classic_game() {
+        let rules = Rules::classic();
+        let [rock, paper, scissors] =
+            ["Rock", "Paper", "Scissors"].map(|s| rules.shape(s).unwrap());
+
+        assert_eq!(rules.outcome(rock, paper), Outcome::Won);
+        assert_eq!(rules.outcome(paper, rock), Outcome::Lost);
+        assert_eq!(rules.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rules.score(scissors, rock), 7);
+        assert_eq!(rules.shape_for_outcome(paper, Outcome::Won), Some(scissors));
+    }
+
+    #[test]
+    fn it_plays_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.shape(name).unwrap();
+
+        let wins = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert!(rules.beats(shape(winner), shape(loser)));
+            assert!(!rules.beats(shape(loser), shape(winner)));
+        }
+        assert_eq!(rules.score(shape("Rock"), shape("Lizard")), 5);
+    }
+
+    #[test]
+    fn it_makes_every_shape_of_a_cyclic_game_win_as_often_as_it_loses() {
+        let names = ["a", "b", "c", "d", "e", "f", "g"];
+        let rules = Rules::cyclic(&names).unwrap();
+
+        for winner in 0..names.len() {
+            let beaten = (0..names.len()).filter(|&loser| rules.beats(winner, loser));
+
+            assert_eq!(beaten.count(), 3);
+        }
+        assert_eq!(
+            Rules::cyclic(&["a", "b"]),
+            Err(RulesError::EvenShapeCount(2))
+        );
+    }
+
+    #[test]
+    fn it_rejects_inconsistent_rules() {
+        let shapes = || vec![Shape::new("Rock", 1), Shape::new("Paper", 2)];
+
+        assert_eq!(Rules::new(vec![], &[]), Err(RulesError::NoShapes));
+        assert_eq!(
+            Rules::new(shapes(), &[(0, 2)]),
+            Err(RulesError::UnknownShape(2))
+        );
+        assert_eq!(
+            Rules::new(shapes(), &[(1, 1)]),
+            Err(RulesError::BeatsItself(1))
+        );
+        assert_eq!(
+            Rules::new(shapes(), &[(1, 0), (0, 1)]),
+            Err(RulesError::BeatEachOther(0, 1))
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{classic_rules, guide_line_from_str, parse_lines, HandShape, Outcome, ParseError};
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+
+/// Meaning given to the X, Y and Z column of the encrypted guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// X, Y and Z are the shapes to play.
+    Shapes([HandShape; 3]),
+    /// X, Y and Z are how the round needs to end.
+    Outcomes([Outcome; 3]),
+}
+
+impl Display for Interpretation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let meanings = match self {
+            Interpretation::Shapes(shapes) => shapes.map(|shape| shape.to_string()),
+            Interpretation::Outcomes(outcomes) => outcomes.map(|outcome| format!("{:?}", outcome)),
+        };
+        write!(
+            f,
+            "X: {}, Y: {}, Z: {}",
+            meanings[0], meanings[1], meanings[2]
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluation {
+    pub interpretation: Interpretation,
+    pub score: u32,
+}
+
+/// Scores the encrypted guide under every possible meaning of its second column.
+pub struct Solver {
+    guide: Vec<(HandShape, usize)>,
+}
+
+impl TryFrom<&str> for Solver {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let guide = parse_lines(input, guide_line_from_str)?;
+        Ok(Solver { guide })
+    }
+}
+
+impl Solver {
+    /// Every mapping of X, Y and Z to shapes, followed by every mapping of X, Y and Z to outcomes.
+    pub fn evaluations(&self) -> Vec<Evaluation> {
+        let shapes = permutations([HandShape::Rock, HandShape::Paper, HandShape::Scissors])
+            .map(Interpretation::Shapes);
+        let outcomes = permutations([Outcome::Lost, Outcome::Draw, Outcome::Won])
+            .map(Interpretation::Outcomes);
+
+        shapes
+            .into_iter()
+            .chain(outcomes)
+            .map(|interpretation| Evaluation {
+                interpretation,
+                score: self.score(interpretation),
+            })
+            .collect()
+    }
+
+    pub fn score(&self, interpretation: Interpretation) -> u32 {
+        let rules = classic_rules();
+
+        self.guide
+            .iter()
+            .map(|(enemy, column)| {
+                let enemy = enemy.index();
+                let myself = match interpretation {
+                    Interpretation::Shapes(shapes) => shapes[*column].index(),
+                    Interpretation::Outcomes(outcomes) => rules
+                        .shape_for_outcome(enemy, outcomes[*column])
+                        .expect("every outcome is possible in the classic game"),
+                };
+                rules.score(enemy, myself)
+            })
+            .sum()
+    }
+
+    /// The interpretation with the highest score, the first one in [`Solver::evaluations`] order
+    /// on ties.
+    pub fn best(&self) -> Evaluation {
+        self.evaluations()
+            .into_iter()
+            .min_by_key(|evaluation| Reverse(evaluation.score))
+            .expect("there are always 12 interpretations")
+    }
+
+    /// The interpretation with the lowest score, the first one in [`Solver::evaluations`] order
+    /// on ties.
+    pub fn worst(&self) -> Evaluation {
+        self.evaluations()
+            .into_iter()
+            .min_by_key(|evaluation| evaluation.score)
+            .expect("there are always 12 interpretations")
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorKind;
+    use advent::Location;
+
+    #[test]
+    fn it_scores_every_interpretation_of_the_guide() {
+        let solver = Solver::try_from(input()).unwrap();
+
+        let evaluations = solver.evaluations();
+
+        assert_eq!(evaluations.len(), 12);
+        assert_eq!(
+            evaluations[0],
+            Evaluation {
+                interpretation: Interpretation::Shapes([
+                    HandShape::Rock,
+                    HandShape::Paper,
+                    HandShape::Scissors
+                ]),
+                score: 15
+            }
+        );
+        assert_eq!(
+            evaluations[6],
+            Evaluation {
+                interpretation: Interpretation::Outcomes([
+                    Outcome::Lost,
+                    Outcome::Draw,
+                    Outcome::Won
+                ]),
+                score: 12
+            }
+        );
+    }
+
+    #[test]
+    fn it_finds_the_best_and_the_worst_interpretation() {
+        let solver = Solver::try_from(input()).unwrap();
+
+        let best = solver.best();
+        let worst = solver.worst();
+
+        assert_eq!(best.score, 24);
+        assert_eq!(
+            best.interpretation.to_string(),
+            "X: Scissors, Y: Paper, Z: Rock"
+        );
+        assert_eq!(worst.score, 6);
+        assert_eq!(
+            worst.interpretation.to_string(),
+            "X: Rock, Y: Scissors, Z: Paper"
+        );
+        assert!(solver
+            .evaluations()
+            .iter()
+            .all(|e| (worst.score..=best.score).contains(&e.score)));
+    }
+
+    #[test]
+    fn it_reports_where_the_guide_is_malformed() {
+        let errors = ["A Y\nD X", "A Y\nB", "A Y\nB W", "A Y\nB X Z"]
+            .map(|input| Solver::try_from(input).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::InvalidEnemyShape("D".to_string()),
+                Location::new(2, 1)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(ParseErrorKind::MissingColumn, Location::new(2, 2))
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::InvalidColumn("W".to_string()),
+                Location::new(2, 3)
+            )
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(ParseErrorKind::TooManyColumns, Location::new(2, 5))
+        );
+    }
+
+    fn input() -> &'static str {
+        "A Y
+B X
+C Z"
+    }
+}
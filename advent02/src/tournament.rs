@@ -0,0 +1,355 @@
+use crate::{HandShape, Outcome, Round};
+
+/// Strategy choosing the shape to play every round of a simulated match.
+pub trait Player {
+    fn name(&self) -> &str;
+
+    fn choose(&mut self) -> HandShape;
+
+    /// Called after every round with the shape the opponent played.
+    fn observe(&mut self, _opponent: HandShape) {}
+
+    /// Called before every match, so the player does not remember previous opponents.
+    fn reset(&mut self) {}
+}
+
+pub struct AlwaysRock;
+
+impl Player for AlwaysRock {
+    fn name(&self) -> &str {
+        "always rock"
+    }
+
+    fn choose(&mut self) -> HandShape {
+        HandShape::Rock
+    }
+}
+
+/// Plays rock, paper and scissors in turns.
+#[derive(Default)]
+pub struct Cycle {
+    next: usize,
+}
+
+impl Player for Cycle {
+    fn name(&self) -> &str {
+        "cycle"
+    }
+
+    fn choose(&mut self) -> HandShape {
+        let shape = [HandShape::Rock, HandShape::Paper, HandShape::Scissors][self.next];
+        self.next = (self.next + 1) % 3;
+        shape
+    }
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+/// Plays the shape beating the one the opponent played the most so far. When several shapes were
+/// played the most, it beats the first of them in rock, paper, scissors order, and it plays rock
+/// until the opponent played anything.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> &str {
+        "frequency counter"
+    }
+
+    fn choose(&mut self) -> HandShape {
+        let shapes = [HandShape::Rock, HandShape::Paper, HandShape::Scissors];
+        let most_played = shapes
+            .into_iter()
+            .rev()
+            .max_by_key(|shape| self.counts[shape.index()])
+            .expect("there are three shapes");
+
+        if self.counts[most_played.index()] == 0 {
+            return HandShape::Rock;
+        }
+        most_played.shape_for_outcome(Outcome::Won)
+    }
+
+    fn observe(&mut self, opponent: HandShape) {
+        self.counts[opponent.index()] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+}
+
+/// Plays the shape the opponent played in the previous round, rock in the first one.
+#[derive(Default)]
+pub struct MirrorLastMove {
+    last: Option<HandShape>,
+}
+
+impl Player for MirrorLastMove {
+    fn name(&self) -> &str {
+        "mirror last move"
+    }
+
+    fn choose(&mut self) -> HandShape {
+        self.last.unwrap_or(HandShape::Rock)
+    }
+
+    fn observe(&mut self, opponent: HandShape) {
+        self.last = Some(opponent);
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Plays random shapes, always the same sequence for the same seed.
+pub struct SeededRandom {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        SeededRandom { seed, state: seed }
+    }
+
+    /// SplitMix64, which is good enough to pick shapes and accepts any seed.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Player for SeededRandom {
+    fn name(&self) -> &str {
+        "seeded random"
+    }
+
+    fn choose(&mut self) -> HandShape {
+        [HandShape::Rock, HandShape::Paper, HandShape::Scissors][(self.next_u64() % 3) as usize]
+    }
+
+    fn reset(&mut self) {
+        self.state = self.seed;
+    }
+}
+
+/// Result of a match, from the point of view of each of the two players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchResult {
+    pub scores: [u32; 2],
+    pub wins: [usize; 2],
+    pub draws: usize,
+}
+
+impl MatchResult {
+    /// Index of the player that won the most rounds, if any.
+    pub fn winner(&self) -> Option<usize> {
+        match self.wins[0].cmp(&self.wins[1]) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Plays `rounds` rounds between two players, scored like the rounds of the guide.
+pub fn play_match(first: &mut dyn Player, second: &mut dyn Player, rounds: usize) -> MatchResult {
+    let mut result = MatchResult {
+        scores: [0; 2],
+        wins: [0; 2],
+        draws: 0,
+    };
+
+    for _ in 0..rounds {
+        let first_shape = first.choose();
+        let second_shape = second.choose();
+
+        let round = Round::new(second_shape, first_shape);
+        result.scores[0] += round.score();
+        result.scores[1] += Round::new(first_shape, second_shape).score();
+        match round.outcome() {
+            Outcome::Won => result.wins[0] += 1,
+            Outcome::Lost => result.wins[1] += 1,
+            Outcome::Draw => result.draws += 1,
+        }
+
+        first.observe(second_shape);
+        second.observe(first_shape);
+    }
+
+    result
+}
+
+/// Matches won, drawn and lost by a player of a tournament, and the score of all its rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    pub score: u32,
+}
+
+impl Standing {
+    /// 3 points for every match won and 1 for every match drawn.
+    pub fn points(&self) -> usize {
+        self.won * 3 + self.drawn
+    }
+}
+
+/// Round-robin tournament, where every player plays a match against every other player.
+pub struct Tournament {
+    players: Vec<Box<dyn Player>>,
+    rounds: usize,
+}
+
+impl Tournament {
+    pub fn new(players: Vec<Box<dyn Player>>, rounds: usize) -> Tournament {
+        Tournament { players, rounds }
+    }
+
+    /// Plays every match and returns the leaderboard, sorted by points and then by score. Players
+    /// tied in both keep the order they were given in.
+    pub fn play(&mut self) -> Vec<Standing> {
+        let mut standings = self
+            .players
+            .iter()
+            .map(|player| Standing {
+                name: player.name().to_string(),
+                won: 0,
+                drawn: 0,
+                lost: 0,
+                score: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for second in 1..self.players.len() {
+            let (before, after) = self.players.split_at_mut(second);
+            for first in 0..second {
+                let (first_player, second_player) = (&mut before[first], &mut after[0]);
+                first_player.reset();
+                second_player.reset();
+
+                let result = play_match(first_player.as_mut(), second_player.as_mut(), self.rounds);
+
+                standings[first].score += result.scores[0];
+                standings[second].score += result.scores[1];
+                match result.winner() {
+                    Some(0) => {
+                        standings[first].won += 1;
+                        standings[second].lost += 1;
+                    }
+                    Some(_) => {
+                        standings[second].won += 1;
+                        standings[first].lost += 1;
+                    }
+                    None => {
+                        standings[first].drawn += 1;
+                        standings[second].drawn += 1;
+                    }
+                }
+            }
+        }
+
+        standings.sort_by(|a, b| {
+            b.points()
+                .cmp(&a.points())
+                .then_with(|| b.score.cmp(&a.score))
+        });
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_plays_a_match_with_the_rules_of_the_guide() {
+        let result = play_match(&mut AlwaysRock, &mut Cycle::default(), 3);
+
+        assert_eq!(
+            result,
+            MatchResult {
+                scores: [12, 15],
+                wins: [1, 1],
+                draws: 1
+            }
+        );
+        assert_eq!(result.winner(), None);
+    }
+
+    #[test]
+    fn it_adapts_to_the_opponent() {
+        let against_counter = play_match(&mut AlwaysRock, &mut FrequencyCounter::default(), 10);
+        let against_mirror = play_match(&mut Cycle::default(), &mut MirrorLastMove::default(), 9);
+
+        assert_eq!(against_counter.wins, [0, 9]);
+        assert_eq!(against_mirror.wins, [8, 0]);
+    }
+
+    #[test]
+    fn it_beats_the_first_of_the_most_played_shapes_on_ties() {
+        let mut counter = FrequencyCounter::default();
+
+        assert_eq!(counter.choose(), HandShape::Rock);
+        counter.observe(HandShape::Scissors);
+        counter.observe(HandShape::Paper);
+        assert_eq!(counter.choose(), HandShape::Scissors);
+        counter.observe(HandShape::Scissors);
+        assert_eq!(counter.choose(), HandShape::Rock);
+    }
+
+    #[test]
+    fn it_plays_the_same_random_shapes_for_the_same_seed() {
+        let mut first = SeededRandom::new(42);
+        let mut second = SeededRandom::new(42);
+
+        let shapes = (0..20).map(|_| first.choose()).collect::<Vec<_>>();
+
+        assert_eq!(shapes, (0..20).map(|_| second.choose()).collect::<Vec<_>>());
+        first.reset();
+        assert_eq!(first.choose(), shapes[0]);
+        assert!(shapes.contains(&HandShape::Rock));
+        assert!(shapes.contains(&HandShape::Paper));
+        assert!(shapes.contains(&HandShape::Scissors));
+    }
+
+    #[test]
+    fn it_ranks_the_players_of_a_round_robin_tournament() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(AlwaysRock),
+            Box::new(Cycle::default()),
+            Box::new(FrequencyCounter::default()),
+            Box::new(MirrorLastMove::default()),
+        ];
+        let mut tournament = Tournament::new(players, 30);
+
+        let leaderboard = tournament.play();
+
+        assert_eq!(
+            leaderboard
+                .iter()
+                .map(|standing| standing.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "cycle",
+                "frequency counter",
+                "always rock",
+                "mirror last move"
+            ]
+        );
+        assert!(leaderboard
+            .iter()
+            .all(|standing| standing.won + standing.drawn + standing.lost == 3));
+    }
+}
@@ -0,0 +1,102 @@
+use advent03::{ElveGroup, Item};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::collections::HashSet;
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `rucksacks` lines of random letters with an even length between 16 and 62.
+fn synthetic_input(rucksacks: usize) -> String {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+
+    (0..rucksacks)
+        .map(|_| {
+            let len = 16 + 2 * (next() % 24);
+            (0..len)
+                .map(|_| LETTERS[next() % LETTERS.len()] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compartments of every rucksack, as one `HashSet` each, which is what `Rucksack` used to do.
+fn hash_set_rucksacks(input: &str) -> Vec<(HashSet<char>, HashSet<char>)> {
+    input
+        .lines()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            (left.chars().collect(), right.chars().collect())
+        })
+        .collect()
+}
+
+/// Sum of the lowest priority shared by every rucksack and by every group of three.
+fn priorities_with_hash_sets(rucksacks: &[(HashSet<char>, HashSet<char>)]) -> u32 {
+    let lowest_priority = |items: &mut dyn Iterator<Item = char>| {
+        items
+            .flat_map(|c| Item::from(c).priority().map(u32::from))
+            .min()
+    };
+
+    let duplicated = rucksacks
+        .iter()
+        .flat_map(|(left, right)| lowest_priority(&mut left.intersection(right).copied()));
+    let badges = rucksacks.chunks(3).flat_map(|group| {
+        group
+            .iter()
+            .map(|(left, right)| left.union(right).copied().collect::<HashSet<_>>())
+            .reduce(|shared, items| shared.intersection(&items).copied().collect())
+            .and_then(|shared| lowest_priority(&mut shared.into_iter()))
+    });
+
+    duplicated.chain(badges).sum()
+}
+
+fn priorities_with_bit_sets(group: &ElveGroup) -> u32 {
+    let duplicated = group
+        .rucksacks()
+        .iter()
+        .flat_map(|rucksack| rucksack.shared_items().priorities().next());
+    let badges = group
+        .badges_per_group_of(3)
+        .flat_map(|badges| badges.priorities().next());
+
+    duplicated.chain(badges).map(u32::from).sum()
+}
+
+fn shared_items(c: &mut Criterion) {
+    let mut group = c.benchmark_group("shared_items");
+
+    for rucksacks in [1_000, 10_000, 100_000] {
+        let input = synthetic_input(rucksacks);
+        let hash_sets = hash_set_rucksacks(&input);
+        let bit_sets = ElveGroup::try_from(input.as_str()).unwrap();
+        assert_eq!(
+            priorities_with_hash_sets(&hash_sets),
+            priorities_with_bit_sets(&bit_sets)
+        );
+        group.throughput(Throughput::Elements(rucksacks as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("hash_set", rucksacks),
+            &hash_sets,
+            |b, rucksacks| b.iter(|| priorities_with_hash_sets(rucksacks)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("bit_set", rucksacks),
+            &bit_sets,
+            |b, group| b.iter(|| priorities_with_bit_sets(group)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, shared_items);
+criterion_main!(benches);
@@ -1,22 +1,42 @@
 #![deny(unused)]
 
-use std::collections::HashSet;
+mod partition;
+mod priority;
+
+use advent::{Location, Solution, Span};
+pub use partition::{BadgeGroup, PartitionError};
+pub use priority::{
+    default_table, PriorityTable, PriorityTableError, UnknownItem, DEFAULT_SPEC, MAX_PRIORITY,
+};
+use std::fmt::{Debug, Display, Formatter};
 use std::slice::Chunks;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    OddLength(usize),
+    InvalidItem(char),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::OddLength(length) => {
+                write!(f, "rucksack length must be even, but was {}", length)
+            }
+            ParseErrorKind::InvalidItem(item) => write!(f, "item '{}' has no priority", item),
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Item(char);
 
 impl Item {
-    pub fn priority(&self) -> u8 {
-        if !self.0.is_ascii() {
-            return 0;
-        }
-
-        if self.0.is_ascii_lowercase() {
-            self.0 as u8 - b'a' + 1
-        } else {
-            self.0 as u8 - b'A' + 27
-        }
+    /// Priority of the item in the [`default_table`], see [`PriorityTable::priority`].
+    pub fn priority(&self) -> Result<u8, UnknownItem> {
+        default_table().priority(*self)
     }
 }
 
@@ -32,72 +52,373 @@ impl From<&Item> for char {
     }
 }
 
-pub struct Rucksack {
-    left: HashSet<Item>,
-    right: HashSet<Item>,
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Set of items, where bit `n` of the mask tells if the item with priority `n` in `table` is in
+/// the set.
+#[derive(Clone, Copy)]
+pub struct ItemSet<'t> {
+    mask: u64,
+    table: &'t PriorityTable,
+}
+
+impl ItemSet<'static> {
+    /// Empty set of items of the [`default_table`].
+    pub fn new() -> ItemSet<'static> {
+        ItemSet::with_table(default_table())
+    }
+}
+
+impl<'t> ItemSet<'t> {
+    /// Empty set of items whose priorities are given by `table`.
+    pub fn with_table(table: &'t PriorityTable) -> ItemSet<'t> {
+        ItemSet { mask: 0, table }
+    }
+
+    /// Set of the items, failing on the first one that is not in `table`.
+    pub fn from_items(
+        items: impl IntoIterator<Item = Item>,
+        table: &'t PriorityTable,
+    ) -> Result<ItemSet<'t>, UnknownItem> {
+        let mut set = ItemSet::with_table(table);
+        for item in items {
+            set.insert(item)?;
+        }
+        Ok(set)
+    }
+
+    pub fn table(&self) -> &'t PriorityTable {
+        self.table
+    }
+
+    /// Adds the item to the set, returning whether it was not in the set yet, or fails if the
+    /// item is not in the table of the set.
+    pub fn insert(&mut self, item: Item) -> Result<bool, UnknownItem> {
+        let priority = self.table.priority(item)?;
+        let added = self.mask & (1 << priority) == 0;
+        self.mask |= 1 << priority;
+        Ok(added)
+    }
+
+    pub fn contains(&self, item: Item) -> bool {
+        self.table
+            .priority(item)
+            .is_ok_and(|priority| self.mask & (1 << priority) != 0)
+    }
+
+    /// Items in both sets, which are expected to have the same table.
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet<'t> {
+        debug_assert!(self.same_table(other), "sets of different tables");
+        ItemSet {
+            mask: self.mask & other.mask,
+            table: self.table,
+        }
+    }
+
+    /// Items in any of the sets, which are expected to have the same table.
+    pub fn union(&self, other: &ItemSet) -> ItemSet<'t> {
+        debug_assert!(self.same_table(other), "sets of different tables");
+        ItemSet {
+            mask: self.mask | other.mask,
+            table: self.table,
+        }
+    }
+
+    fn same_table(&self, other: &ItemSet) -> bool {
+        std::ptr::eq(self.table, other.table) || self.table == other.table
+    }
+
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Priorities of the items in the set, from the lowest to the highest.
+    pub fn priorities(&self) -> impl Iterator<Item = u8> {
+        let mask = self.mask;
+        (1..=MAX_PRIORITY).filter(move |priority| mask & (1 << priority) != 0)
+    }
+
+    /// Items of the set, from the lowest priority to the highest.
+    pub fn iter(&self) -> impl Iterator<Item = Item> + 't {
+        let table = self.table;
+        self.priorities()
+            .filter_map(move |priority| table.item(priority))
+    }
+}
+
+impl Default for ItemSet<'static> {
+    fn default() -> Self {
+        ItemSet::new()
+    }
+}
+
+impl PartialEq for ItemSet<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mask == other.mask && self.same_table(other)
+    }
+}
+
+impl Eq for ItemSet<'_> {}
+
+impl Debug for ItemSet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Rucksack with two compartments of the same number of items, whose priorities are given by
+/// the table it was parsed with.
+pub struct Rucksack<'t> {
+    left: ItemSet<'t>,
+    right: ItemSet<'t>,
 }
 
-impl TryFrom<&str> for Rucksack {
-    type Error = String;
+impl TryFrom<&str> for Rucksack<'static> {
+    type Error = ParseError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        if input.len() % 2 == 1 {
-            return Err(format!(
-                "Input length must be even, but was {}",
-                input.len()
-            ));
+        Rucksack::parse_with(input, default_table())
+    }
+}
+
+impl<'t> Rucksack<'t> {
+    pub fn parse_with(input: &str, table: &'t PriorityTable) -> Result<Rucksack<'t>, ParseError> {
+        let length = input.chars().count();
+        if length % 2 == 1 {
+            let kind = ParseErrorKind::OddLength(length);
+            return Err(ParseError::new(kind, Location::new(1, 1)));
         }
 
-        let (left, right) = input.split_at(input.len() / 2);
+        let middle = input
+            .char_indices()
+            .nth(length / 2)
+            .map_or(input.len(), |(i, _)| i);
+        let (left, right) = input.split_at(middle);
 
         Ok(Rucksack {
-            left: left.chars().map(Item::from).collect(),
-            right: right.chars().map(Item::from).collect(),
+            left: item_set_from_str(input, left, table)?,
+            right: item_set_from_str(input, right, table)?,
         })
     }
-}
 
-impl Rucksack {
-    fn left(&self) -> HashSet<char> {
-        self.left.iter().map(char::from).collect()
+    pub fn left(&self) -> ItemSet<'t> {
+        self.left
     }
 
-    fn right(&self) -> HashSet<char> {
-        self.right.iter().map(char::from).collect()
+    pub fn right(&self) -> ItemSet<'t> {
+        self.right
     }
 
+    /// Every item in the rucksack, in any of its compartments.
+    pub fn items(&self) -> ItemSet<'t> {
+        self.left.union(&self.right)
+    }
+
+    /// Items found in both compartments, which should be exactly one.
+    pub fn shared_items(&self) -> ItemSet<'t> {
+        self.left.intersection(&self.right)
+    }
+
+    /// The shared item with the lowest priority, if any. See [`Rucksack::shared_items`] to find
+    /// out whether there are more.
     pub fn find_duplicated_element(&self) -> Option<Item> {
-        self.left.intersection(&self.right).next().cloned()
+        self.shared_items().iter().next()
+    }
+}
+
+/// Items of `compartment`, which is a slice of the rucksack `line`.
+fn item_set_from_str<'t>(
+    line: &str,
+    compartment: &str,
+    table: &'t PriorityTable,
+) -> Result<ItemSet<'t>, ParseError> {
+    let mut set = ItemSet::with_table(table);
+
+    for (i, c) in compartment.char_indices() {
+        set.insert(Item::from(c)).map_err(|_| {
+            let token = &compartment[i..i + c.len_utf8()];
+            let kind = ParseErrorKind::InvalidItem(c);
+            ParseError::new(kind, Span::of(1, line, token))
+        })?;
+    }
+
+    Ok(set)
+}
+
+/// Rucksack or group of rucksacks that does not share exactly one item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anomaly {
+    /// The compartments of the rucksack at `line` share `items`.
+    Rucksack { line: usize, items: Vec<Item> },
+    /// The rucksacks from `first_line` to `last_line` share `badges`.
+    Group {
+        first_line: usize,
+        last_line: usize,
+        badges: Vec<Item>,
+    },
+    /// The last group, from `first_line` to `last_line`, has less rucksacks than the others.
+    IncompleteGroup { first_line: usize, last_line: usize },
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Anomaly::Rucksack { line, items } => write!(
+                f,
+                "rucksack at line {} has {} shared items{}",
+                line,
+                items.len(),
+                items_list(items)
+            ),
+            Anomaly::Group {
+                first_line,
+                last_line,
+                badges,
+            } => write!(
+                f,
+                "group at lines {}-{} has {} badges{}",
+                first_line,
+                last_line,
+                badges.len(),
+                items_list(badges)
+            ),
+            Anomaly::IncompleteGroup {
+                first_line,
+                last_line,
+            } => write!(
+                f,
+                "group at lines {}-{} is incomplete",
+                first_line, last_line
+            ),
+        }
+    }
+}
+
+fn items_list(items: &[Item]) -> String {
+    if items.is_empty() {
+        return String::new();
     }
+
+    let items = items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    format!(": {}", items.join(", "))
 }
 
-pub struct ElveGroup {
-    rucksacks: Vec<Rucksack>,
+pub struct ElveGroup<'t> {
+    rucksacks: Vec<Rucksack<'t>>,
+    table: &'t PriorityTable,
 }
 
-impl TryFrom<&str> for ElveGroup {
-    type Error = String;
+impl TryFrom<&str> for ElveGroup<'static> {
+    type Error = ParseError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
+        ElveGroup::parse_with(input, default_table())
+    }
+}
+
+impl<'t> ElveGroup<'t> {
+    /// Rucksacks of every line of `input`, whose priorities are given by `table`.
+    pub fn parse_with(input: &str, table: &'t PriorityTable) -> Result<ElveGroup<'t>, ParseError> {
         let rucksacks = input
             .lines()
-            .map(Rucksack::try_from)
+            .enumerate()
+            .map(|(i, line)| Rucksack::parse_with(line, table).map_err(|e| e.with_line_offset(i)))
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(ElveGroup { rucksacks })
+        Ok(ElveGroup { rucksacks, table })
+    }
+
+    pub fn rucksacks(&self) -> &[Rucksack<'t>] {
+        &self.rucksacks
+    }
+
+    /// Groups of `num` consecutive rucksacks, where there are none if `num` is 0.
+    fn groups_of(&self, num: usize) -> Chunks<'_, Rucksack<'t>> {
+        let rucksacks = if num == 0 { &[] } else { &self.rucksacks[..] };
+        rucksacks.chunks(num.max(1))
     }
-}
 
-impl ElveGroup {
-    pub fn duplicated_element_per_group_of(&self, num: usize) -> DuplicatedElementIter {
+    pub fn duplicated_element_per_group_of(&self, num: usize) -> DuplicatedElementIter<'_> {
         DuplicatedElementIter {
-            iter: self.rucksacks.chunks(num),
+            iter: self.groups_of(num),
+            table: self.table,
+        }
+    }
+
+    /// Items shared by all the rucksacks of every group of `num` consecutive rucksacks, which is
+    /// nothing if `num` is 0.
+    pub fn badges_per_group_of(&self, num: usize) -> impl Iterator<Item = ItemSet<'t>> + '_ {
+        self.groups_of(num).map(|group| badges(group, self.table))
+    }
+
+    /// Every rucksack whose compartments do not share exactly one item, and every group of `num`
+    /// consecutive rucksacks that does not share exactly one badge, in the order of the input.
+    /// There are no groups if `num` is 0, so only rucksacks are reported then.
+    pub fn anomalies(&self, num: usize) -> Vec<Anomaly> {
+        let mut anomalies = Vec::new();
+        let size = num.max(1);
+
+        for (i, group) in self.rucksacks.chunks(size).enumerate() {
+            let first_line = i * size + 1;
+            let last_line = first_line + group.len() - 1;
+
+            for (line, rucksack) in (first_line..).zip(group) {
+                let items = rucksack.shared_items();
+                if items.len() != 1 {
+                    let items = items.iter().collect();
+                    anomalies.push(Anomaly::Rucksack { line, items });
+                }
+            }
+
+            if num == 0 {
+                continue;
+            }
+            if group.len() < num {
+                anomalies.push(Anomaly::IncompleteGroup {
+                    first_line,
+                    last_line,
+                });
+                continue;
+            }
+
+            let badges = badges(group, self.table);
+            if badges.len() != 1 {
+                let badges = badges.iter().collect();
+                anomalies.push(Anomaly::Group {
+                    first_line,
+                    last_line,
+                    badges,
+                });
+            }
         }
+
+        anomalies
     }
 }
 
+/// Items found in every rucksack of the group, whose items are in `table`.
+fn badges<'t>(group: &[Rucksack<'t>], table: &'t PriorityTable) -> ItemSet<'t> {
+    group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(|shared, items| shared.intersection(&items))
+        .unwrap_or(ItemSet::with_table(table))
+}
+
 pub struct DuplicatedElementIter<'a> {
-    iter: Chunks<'a, Rucksack>,
+    iter: Chunks<'a, Rucksack<'a>>,
+    table: &'a PriorityTable,
 }
 
 impl<'a> Iterator for DuplicatedElementIter<'a> {
@@ -105,31 +426,44 @@ impl<'a> Iterator for DuplicatedElementIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let chunk = self.iter.next()?;
-        let mut set = Vec::new();
-
-        for rucksack in chunk {
-            let rucksack_set = rucksack
-                .left()
-                .union(&rucksack.right())
-                .cloned()
-                .map(Item::from)
-                .collect::<HashSet<Item>>();
-            set.push(rucksack_set);
-        }
+        badges(chunk, self.table).iter().next()
+    }
+}
 
-        let mut intersection = set[0].clone();
-        for rucksack_set in set {
-            intersection = intersection.intersection(&rucksack_set).cloned().collect();
-        }
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = ElveGroup<'static>;
+    type Error = ParseError;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        ElveGroup::try_from(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(input
+            .rucksacks
+            .iter()
+            .flat_map(|rucksack| rucksack.shared_items().priorities().next())
+            .map(u32::from)
+            .sum())
+    }
 
-        intersection.iter().next().cloned()
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(input
+            .badges_per_group_of(3)
+            .flat_map(|badges| badges.priorities().next())
+            .map(u32::from)
+            .sum())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
+    use std::str::FromStr;
 
     #[test]
     fn it_retrieves_the_set_of_elements_of_each_rucksack() {
@@ -137,12 +471,18 @@ mod tests {
         let rucksack = Rucksack::try_from(input).unwrap();
 
         assert_eq!(
-            rucksack.left(),
-            HashSet::from(['v', 'J', 'r', 'w', 'p', 'W', 't', 'g', 'W', 'r'])
+            Ok(rucksack.left()),
+            ItemSet::from_items(
+                ['v', 'J', 'r', 'w', 'p', 'W', 't', 'g', 'W', 'r'].map(Item::from),
+                default_table()
+            )
         );
         assert_eq!(
-            rucksack.right(),
-            HashSet::from(['h', 'c', 's', 'F', 'M', 'M', 'f', 'F', 'F', 'h', 'F', 'p'])
+            Ok(rucksack.right()),
+            ItemSet::from_items(
+                ['h', 'c', 's', 'F', 'M', 'M', 'f', 'F', 'F', 'h', 'F', 'p'].map(Item::from),
+                default_table()
+            )
         );
     }
 
@@ -160,9 +500,9 @@ mod tests {
         let a_to_z_upper = ('A'..='Z').map(Item::from);
         let items = a_to_z_lower.chain(a_to_z_upper).collect::<Vec<Item>>();
 
-        let items_priorities = items.iter().map(Item::priority).collect::<Vec<u8>>();
+        let items_priorities = items.iter().map(Item::priority).collect::<Vec<_>>();
 
-        assert_eq!(items_priorities, (1..=52).collect::<Vec<u8>>());
+        assert_eq!(items_priorities, (1..=52).map(Ok).collect::<Vec<_>>());
     }
 
     #[test]
@@ -171,7 +511,7 @@ mod tests {
         let rucksack = Rucksack::try_from(input).unwrap();
         let duplicated = rucksack.find_duplicated_element().unwrap();
 
-        assert_eq!(duplicated.priority(), 16);
+        assert_eq!(duplicated.priority(), Ok(16));
     }
 
     #[test]
@@ -182,12 +522,151 @@ mod tests {
             .collect::<Vec<Rucksack>>();
 
         let duplicated_elements = rucksacks.iter().flat_map(Rucksack::find_duplicated_element);
-        let priorities = duplicated_elements.map(|item| item.priority() as u32);
+        let priorities = duplicated_elements.flat_map(|item| item.priority().map(u32::from));
         let priority_sum = priorities.sum::<u32>();
 
         assert_eq!(priority_sum, 157);
     }
 
+    #[test]
+    fn it_reports_the_line_of_a_rucksack_with_odd_length() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsS";
+
+        let error = ElveGroup::try_from(input).err().unwrap();
+
+        assert_eq!(error.kind(), &ParseErrorKind::OddLength(31));
+        assert_eq!(error.location(), Location::new(2, 1));
+    }
+
+    #[test]
+    fn it_stores_items_as_bits_by_priority() {
+        let mut set = ItemSet::new();
+
+        assert_eq!(set.insert(Item::from('a')), Ok(true));
+        assert_eq!(set.insert(Item::from('Z')), Ok(true));
+        assert_eq!(set.insert(Item::from('a')), Ok(false));
+        assert_eq!(set.insert(Item::from('1')), Err(UnknownItem('1')));
+        assert_eq!(set.insert(Item::from('é')), Err(UnknownItem('é')));
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(Item::from('Z')));
+        assert!(!set.contains(Item::from('b')));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Item::from('a'), Item::from('Z')]
+        );
+        let z = ItemSet::from_items([Item::from('Z')], default_table()).unwrap();
+        assert_eq!(set.intersection(&z), z);
+    }
+
+    #[test]
+    fn it_reports_items_that_are_not_letters() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrs1MfFZSrLrFZsSL";
+
+        let error = ElveGroup::try_from(input).err().unwrap();
+
+        assert_eq!(
+            error,
+            ParseError::new(ParseErrorKind::InvalidItem('1'), Location::new(2, 19))
+        );
+    }
+
+    #[test]
+    fn it_finds_every_shared_item() {
+        let rucksack = Rucksack::try_from("abcdbcae").unwrap();
+        let group = ElveGroup::try_from(input()).unwrap();
+
+        assert_eq!(
+            rucksack.shared_items().iter().collect::<Vec<_>>(),
+            vec![Item::from('a'), Item::from('b'), Item::from('c')]
+        );
+        assert_eq!(rucksack.find_duplicated_element(), Some(Item::from('a')));
+        assert_eq!(
+            group
+                .badges_per_group_of(3)
+                .map(|badges| badges.iter().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![Item::from('r')], vec![Item::from('Z')]]
+        );
+    }
+
+    #[test]
+    fn it_has_no_anomalies_in_a_valid_input() {
+        let group = ElveGroup::try_from(input()).unwrap();
+
+        assert_eq!(group.anomalies(3), vec![]);
+    }
+
+    #[test]
+    fn it_reports_rucksacks_and_groups_without_exactly_one_shared_item() {
+        let input = "abcdbcae\nabcdefgh\nabzb\nxyzy\nxx";
+        let group = ElveGroup::try_from(input).unwrap();
+
+        let anomalies = group.anomalies(3);
+
+        assert_eq!(
+            anomalies.iter().map(Anomaly::to_string).collect::<Vec<_>>(),
+            vec![
+                "rucksack at line 1 has 3 shared items: a, b, c",
+                "rucksack at line 2 has 0 shared items",
+                "group at lines 1-3 has 2 badges: a, b",
+                "group at lines 4-5 is incomplete",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_has_no_groups_of_zero_rucksacks() {
+        let group = ElveGroup::try_from("abcdbcae\nabcb").unwrap();
+
+        assert_eq!(group.duplicated_element_per_group_of(0).count(), 0);
+        assert_eq!(group.badges_per_group_of(0).count(), 0);
+        assert_eq!(
+            group.anomalies(0),
+            vec![Anomaly::Rucksack {
+                line: 1,
+                items: vec![Item::from('a'), Item::from('b'), Item::from('c')]
+            }]
+        );
+    }
+
+    #[test]
+    fn it_uses_the_priorities_of_the_given_table() {
+        let table = PriorityTable::from_str("0-9 α-ω").unwrap();
+        let input = "1αβ2\n12βγ\nβ5β6";
+
+        let group = ElveGroup::parse_with(input, &table).unwrap();
+
+        assert_eq!(
+            group
+                .rucksacks()
+                .iter()
+                .map(|rucksack| rucksack.find_duplicated_element())
+                .collect::<Vec<_>>(),
+            vec![None, None, Some(Item::from('β'))]
+        );
+        assert_eq!(
+            group.duplicated_element_per_group_of(3).collect::<Vec<_>>(),
+            vec![Item::from('β')]
+        );
+        let shared = group.rucksacks()[2].shared_items();
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![Item::from('β')]);
+        assert!(shared.contains(Item::from('β')));
+        assert!(!shared.contains(Item::from('l')));
+        assert_eq!(
+            group.badges_per_group_of(3).collect::<Vec<_>>(),
+            vec![ItemSet::from_items([Item::from('β')], &table).unwrap()]
+        );
+        assert_eq!(
+            ItemSet::with_table(&table).insert(Item::from('a')),
+            Err(UnknownItem('a'))
+        );
+        assert_eq!(
+            ElveGroup::try_from(input).err().unwrap().to_string(),
+            "item '1' has no priority at line 1, column 1"
+        );
+    }
+
     fn input() -> &'static str {
         "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
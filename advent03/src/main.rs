@@ -11,7 +11,8 @@ fn sum_priorities() {
     let priorities = rucksacks.flat_map(|rucksack| {
         rucksack
             .find_duplicated_element()
-            .map(|item| item.priority() as u32)
+            .and_then(|item| item.priority().ok())
+            .map(u32::from)
     });
 
     let priority_sum = priorities.sum::<u32>();
@@ -21,7 +22,10 @@ fn sum_priorities() {
 fn sum_priorities_with_elve_groups() {
     let elve_group = ElveGroup::try_from(input()).unwrap();
     let iter = elve_group.duplicated_element_per_group_of(3);
-    let priorities = iter.map(|item| item.priority() as u32).sum::<u32>();
+    let priorities = iter
+        .flat_map(|item| item.priority().ok())
+        .map(u32::from)
+        .sum::<u32>();
 
     println!("Sum of priorities from groups: {}", priorities);
 }
@@ -0,0 +1,247 @@
+use crate::{ElveGroup, Item, ItemSet};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Group of rucksacks sharing exactly one badge, found by [`ElveGroup::partition_into_groups_of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadgeGroup {
+    /// Indexes of the rucksacks of the group in [`ElveGroup::rucksacks`], in increasing order.
+    pub rucksacks: Vec<usize>,
+    pub badge: Item,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionError {
+    EmptyGroups,
+    UnevenGroups {
+        rucksacks: usize,
+        size: usize,
+    },
+    /// Every way of grouping the rucksacks was tried, and none of them gives every group exactly
+    /// one badge.
+    NoPartition,
+}
+
+impl Display for PartitionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartitionError::EmptyGroups => write!(f, "groups need at least one rucksack"),
+            PartitionError::UnevenGroups { rucksacks, size } => write!(
+                f,
+                "{} rucksacks cannot be split in groups of {}",
+                rucksacks, size
+            ),
+            PartitionError::NoPartition => write!(
+                f,
+                "the rucksacks cannot be grouped so that every group has exactly one badge"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PartitionError {}
+
+impl<'t> ElveGroup<'t> {
+    /// Splits the rucksacks, in any order, in groups of `size` sharing exactly one badge each.
+    ///
+    /// The search is exhaustive, so [`PartitionError::NoPartition`] means that no such grouping
+    /// exists. When there are several, the one keeping the rucksacks closest to the order of the
+    /// input is returned.
+    pub fn partition_into_groups_of(&self, size: usize) -> Result<Vec<BadgeGroup>, PartitionError> {
+        if size == 0 {
+            return Err(PartitionError::EmptyGroups);
+        }
+        if !self.rucksacks.len().is_multiple_of(size) {
+            return Err(PartitionError::UnevenGroups {
+                rucksacks: self.rucksacks.len(),
+                size,
+            });
+        }
+
+        let mut search = Search {
+            items: self
+                .rucksacks
+                .iter()
+                .map(|rucksack| rucksack.items())
+                .collect(),
+            size,
+            used: vec![false; self.rucksacks.len()],
+            groups: Vec::new(),
+            dead_ends: HashSet::new(),
+        };
+        if !search.complete() {
+            return Err(PartitionError::NoPartition);
+        }
+
+        Ok(search
+            .groups
+            .into_iter()
+            .map(|(rucksacks, badges)| BadgeGroup {
+                rucksacks,
+                badge: badges
+                    .iter()
+                    .next()
+                    .expect("the badge was read with the table of the set"),
+            })
+            .collect())
+    }
+}
+
+/// Backtracking over the groups the first rucksack not grouped yet can be in.
+struct Search<'t> {
+    items: Vec<ItemSet<'t>>,
+    size: usize,
+    used: Vec<bool>,
+    groups: Vec<(Vec<usize>, ItemSet<'t>)>,
+    /// Rucksacks already grouped when the rest could not be grouped, which does not depend on how
+    /// they were grouped.
+    dead_ends: HashSet<Vec<bool>>,
+}
+
+impl<'t> Search<'t> {
+    fn complete(&mut self) -> bool {
+        let Some(first) = self.used.iter().position(|used| !used) else {
+            return true;
+        };
+        if self.dead_ends.contains(&self.used) {
+            return false;
+        }
+
+        self.used[first] = true;
+        let shared = self.items[first];
+        if self.extend(&mut vec![first], shared, first + 1) {
+            return true;
+        }
+        self.used[first] = false;
+
+        self.dead_ends.insert(self.used.clone());
+        false
+    }
+
+    /// Adds the rucksacks from `from` onwards to `members`, while they still share some item.
+    fn extend(&mut self, members: &mut Vec<usize>, shared: ItemSet<'t>, from: usize) -> bool {
+        if members.len() == self.size {
+            if shared.len() != 1 {
+                return false;
+            }
+            self.groups.push((members.clone(), shared));
+            if self.complete() {
+                return true;
+            }
+            self.groups.pop();
+            return false;
+        }
+
+        for next in from..self.items.len() {
+            if self.used[next] {
+                continue;
+            }
+            let shared = shared.intersection(&self.items[next]);
+            if shared.is_empty() {
+                continue;
+            }
+
+            self.used[next] = true;
+            members.push(next);
+            if self.extend(members, shared, next + 1) {
+                return true;
+            }
+            members.pop();
+            self.used[next] = false;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_groups_of_shuffled_rucksacks() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+ttgJtRGJQctTZtZT
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+PmmdzqPrVvPwwTWBwg
+CrZsJsPPZsGzwwsLwLmpwMDw";
+        let group = ElveGroup::try_from(input).unwrap();
+
+        let partition = group.partition_into_groups_of(3).unwrap();
+
+        assert_eq!(
+            partition,
+            vec![
+                BadgeGroup {
+                    rucksacks: vec![0, 1, 5],
+                    badge: Item::from('J')
+                },
+                BadgeGroup {
+                    rucksacks: vec![2, 3, 4],
+                    badge: Item::from('q')
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_backtracks_when_the_first_grouping_fails() {
+        let group = ElveGroup::try_from("ab\nac\nbx\ncy").unwrap();
+
+        let partition = group.partition_into_groups_of(2).unwrap();
+
+        assert_eq!(
+            partition
+                .iter()
+                .map(|group| (group.rucksacks.clone(), group.badge))
+                .collect::<Vec<_>>(),
+            vec![(vec![0, 2], Item::from('b')), (vec![1, 3], Item::from('c'))]
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_rucksacks_in_order_when_possible() {
+        let rucksacks = ElveGroup::try_from("ab\nac\nde\ndf").unwrap();
+
+        assert_eq!(
+            rucksacks.partition_into_groups_of(2),
+            Ok(vec![
+                BadgeGroup {
+                    rucksacks: vec![0, 1],
+                    badge: Item::from('a')
+                },
+                BadgeGroup {
+                    rucksacks: vec![2, 3],
+                    badge: Item::from('d')
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn it_proves_when_there_is_no_partition() {
+        let rucksacks = ElveGroup::try_from("ab\nac\nde\ndf").unwrap();
+        let two_badges = ElveGroup::try_from("ab\nab").unwrap();
+
+        assert_eq!(
+            rucksacks.partition_into_groups_of(4),
+            Err(PartitionError::NoPartition)
+        );
+        assert_eq!(
+            two_badges.partition_into_groups_of(2),
+            Err(PartitionError::NoPartition)
+        );
+        assert_eq!(
+            rucksacks.partition_into_groups_of(3),
+            Err(PartitionError::UnevenGroups {
+                rucksacks: 4,
+                size: 3
+            })
+        );
+        assert_eq!(
+            rucksacks.partition_into_groups_of(0),
+            Err(PartitionError::EmptyGroups)
+        );
+    }
+}
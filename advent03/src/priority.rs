@@ -0,0 +1,214 @@
+use crate::Item;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Highest priority an item can have, so every item fits in the bits of an `ItemSet`.
+pub const MAX_PRIORITY: u8 = 63;
+
+/// Spec of the table used by the puzzle: `a` to `z` have priorities 1 to 26, and `A` to `Z` have
+/// priorities 27 to 52.
+pub const DEFAULT_SPEC: &str = "a-z A-Z";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownItem(pub char);
+
+impl Display for UnknownItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "item '{}' has no priority", self.0)
+    }
+}
+
+impl std::error::Error for UnknownItem {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityTableError {
+    Empty,
+    InvalidRange(String),
+    DuplicatedItem(char),
+    TooManyItems(usize),
+}
+
+impl Display for PriorityTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriorityTableError::Empty => write!(f, "the priority table has no items"),
+            PriorityTableError::InvalidRange(range) => {
+                write!(f, "'{}' is not a range like 'a-z'", range)
+            }
+            PriorityTableError::DuplicatedItem(item) => {
+                write!(f, "item '{}' is in the priority table more than once", item)
+            }
+            PriorityTableError::TooManyItems(count) => write!(
+                f,
+                "the priority table has {} items, but at most {} are supported",
+                count, MAX_PRIORITY
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PriorityTableError {}
+
+/// Priority of every known item, starting at 1 and following the order the items were given in.
+///
+/// Tables are loaded from a spec of whitespace separated tokens, where every token is either a
+/// single item or an inclusive range of items like `a-z`. Items can be any Unicode character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityTable {
+    items: Vec<char>,
+    ascii: [u8; 128],
+    others: HashMap<char, u8>,
+}
+
+impl PriorityTable {
+    pub fn new(items: impl IntoIterator<Item = char>) -> Result<PriorityTable, PriorityTableError> {
+        let mut table = PriorityTable {
+            items: Vec::new(),
+            ascii: [0; 128],
+            others: HashMap::new(),
+        };
+
+        for item in items {
+            if table.priority(Item::from(item)).is_ok() {
+                return Err(PriorityTableError::DuplicatedItem(item));
+            }
+
+            table.items.push(item);
+            let priority = match u8::try_from(table.items.len()) {
+                Ok(priority) if priority <= MAX_PRIORITY => priority,
+                _ => return Err(PriorityTableError::TooManyItems(table.items.len())),
+            };
+            match table.ascii.get_mut(item as usize) {
+                Some(slot) => *slot = priority,
+                None => {
+                    table.others.insert(item, priority);
+                }
+            }
+        }
+
+        if table.items.is_empty() {
+            return Err(PriorityTableError::Empty);
+        }
+        Ok(table)
+    }
+
+    pub fn priority(&self, item: Item) -> Result<u8, UnknownItem> {
+        let c = char::from(&item);
+        let priority = match self.ascii.get(c as usize) {
+            Some(&priority) => priority,
+            None => self.others.get(&c).copied().unwrap_or(0),
+        };
+
+        if priority == 0 {
+            return Err(UnknownItem(c));
+        }
+        Ok(priority)
+    }
+
+    pub fn item(&self, priority: u8) -> Option<Item> {
+        let index = usize::from(priority).checked_sub(1)?;
+        self.items.get(index).copied().map(Item::from)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl FromStr for PriorityTable {
+    type Err = PriorityTableError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::new();
+
+        for token in spec.split_whitespace() {
+            let mut chars = token.chars();
+            match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                (Some(item), None, None, None) => items.push(item),
+                (Some(first), Some('-'), Some(last), None) if first <= last => {
+                    items.extend(first..=last)
+                }
+                _ => return Err(PriorityTableError::InvalidRange(token.to_string())),
+            }
+        }
+
+        PriorityTable::new(items)
+    }
+}
+
+impl Default for PriorityTable {
+    fn default() -> Self {
+        DEFAULT_SPEC.parse().expect("the default spec is valid")
+    }
+}
+
+/// The [`PriorityTable::default`] table, shared by everything parsed without a table.
+pub fn default_table() -> &'static PriorityTable {
+    static DEFAULT: OnceLock<PriorityTable> = OnceLock::new();
+    DEFAULT.get_or_init(PriorityTable::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_gives_letters_the_puzzle_priorities_by_default() {
+        let table = PriorityTable::default();
+
+        assert_eq!(table.priority(Item::from('a')), Ok(1));
+        assert_eq!(table.priority(Item::from('Z')), Ok(52));
+        assert_eq!(table.item(27), Some(Item::from('A')));
+        assert_eq!(table.item(0), None);
+        assert_eq!(table.item(53), None);
+    }
+
+    #[test]
+    fn it_rejects_unknown_items_instead_of_panicking() {
+        let table = PriorityTable::default();
+
+        assert_eq!(table.priority(Item::from('1')), Err(UnknownItem('1')));
+        assert_eq!(table.priority(Item::from('@')), Err(UnknownItem('@')));
+        assert_eq!(table.priority(Item::from('é')), Err(UnknownItem('é')));
+        assert_eq!(table.priority(Item::from('🎁')), Err(UnknownItem('🎁')));
+    }
+
+    #[test]
+    fn it_loads_a_table_with_unicode_items_from_a_spec() {
+        let table = PriorityTable::from_str("0-9 α-ω 🎁 -").unwrap();
+
+        assert_eq!(table.priority(Item::from('0')), Ok(1));
+        assert_eq!(table.priority(Item::from('α')), Ok(11));
+        assert_eq!(table.priority(Item::from('🎁')), Ok(36));
+        assert_eq!(table.priority(Item::from('-')), Ok(37));
+        assert_eq!(table.item(36), Some(Item::from('🎁')));
+        assert_eq!(table.priority(Item::from('a')), Err(UnknownItem('a')));
+    }
+
+    #[test]
+    fn it_rejects_invalid_specs() {
+        assert_eq!(PriorityTable::from_str(" "), Err(PriorityTableError::Empty));
+        assert_eq!(
+            PriorityTable::from_str("z-a"),
+            Err(PriorityTableError::InvalidRange("z-a".to_string()))
+        );
+        assert_eq!(
+            PriorityTable::from_str("ab"),
+            Err(PriorityTableError::InvalidRange("ab".to_string()))
+        );
+        assert_eq!(
+            PriorityTable::from_str("a-z x"),
+            Err(PriorityTableError::DuplicatedItem('x'))
+        );
+        assert_eq!(
+            PriorityTable::from_str("a-z A-Z 0-9 !-#"),
+            Err(PriorityTableError::TooManyItems(64))
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{Range, SectionAssignments};
+use std::cmp::Reverse;
+use std::ops::RangeInclusive;
+
+/// Interval tree over the ranges of every elf of a [`SectionAssignments`].
+///
+/// Elves are numbered in the order of the input, so the elves of the pair at line `n` are `2n - 2`
+/// and `2n - 1`. The tree is a centered interval tree, where every node keeps the elves cleaning
+/// its center section, and the elves are also sorted by the start of their range. Queries take
+/// `O(log n + k)` to find `k` elves, which come in no particular order.
+pub struct SectionIndex {
+    elf_ranges: Vec<Range>,
+    /// Every elf, sorted by the start of its range.
+    by_start: Vec<usize>,
+    /// End of every range, sorted.
+    ends: Vec<u32>,
+    /// Nodes of the tree, starting with the root.
+    nodes: Vec<Node>,
+}
+
+/// Node of the tree, with the elves cleaning the `center` section. Elves ending before it are in
+/// the `left` subtree, and elves starting after it in the `right` one.
+struct Node {
+    center: u32,
+    /// Elves of the node, sorted by increasing start.
+    by_start: Vec<usize>,
+    /// Elves of the node, sorted by decreasing end.
+    by_end: Vec<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl SectionIndex {
+    fn new(elf_ranges: Vec<Range>) -> SectionIndex {
+        let mut by_start = (0..elf_ranges.len()).collect::<Vec<_>>();
+        by_start.sort_by_key(|&elf| elf_ranges[elf].start);
+        let mut ends = elf_ranges.iter().map(|range| range.end).collect::<Vec<_>>();
+        ends.sort_unstable();
+
+        let mut nodes = Vec::new();
+        Self::build(&elf_ranges, by_start.clone(), &mut nodes);
+
+        SectionIndex {
+            elf_ranges,
+            by_start,
+            ends,
+            nodes,
+        }
+    }
+
+    /// Adds the subtree of the elves, sorted by start, centered on the start of the median one,
+    /// so each subtree has at most half of them.
+    fn build(elf_ranges: &[Range], elves: Vec<usize>, nodes: &mut Vec<Node>) -> Option<usize> {
+        let center = elf_ranges[*elves.get(elves.len() / 2)?].start;
+
+        let (mut left, mut by_start, mut right) = (Vec::new(), Vec::new(), Vec::new());
+        for elf in elves {
+            match elf_ranges[elf] {
+                range if range.end < center => left.push(elf),
+                range if range.start > center => right.push(elf),
+                _ => by_start.push(elf),
+            }
+        }
+        let mut by_end = by_start.clone();
+        by_end.sort_by_key(|&elf| Reverse(elf_ranges[elf].end));
+
+        let node = nodes.len();
+        nodes.push(Node {
+            center,
+            by_start,
+            by_end,
+            left: None,
+            right: None,
+        });
+        nodes[node].left = Self::build(elf_ranges, left, nodes);
+        nodes[node].right = Self::build(elf_ranges, right, nodes);
+        Some(node)
+    }
+
+    /// Number of elves.
+    pub fn len(&self) -> usize {
+        self.elf_ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elf_ranges.is_empty()
+    }
+
+    /// Elves cleaning the section.
+    pub fn stab(&self, section: u32) -> Vec<usize> {
+        let mut elves = Vec::new();
+        let mut next = (!self.nodes.is_empty()).then_some(0);
+
+        while let Some(node) = next.map(|node| &self.nodes[node]) {
+            // Every elf of the node cleans the center, so it cleans the section when it starts
+            // before it, or ends after it.
+            if section < node.center {
+                let starting = |&&elf: &&usize| self.elf_ranges[elf].start <= section;
+                elves.extend(node.by_start.iter().take_while(starting));
+                next = node.left;
+            } else {
+                let ending = |&&elf: &&usize| self.elf_ranges[elf].end >= section;
+                elves.extend(node.by_end.iter().take_while(ending));
+                next = node.right;
+            }
+        }
+        elves
+    }
+
+    /// Elves cleaning any of the sections.
+    pub fn overlapping(&self, sections: RangeInclusive<u32>) -> Vec<usize> {
+        let (start, end) = sections.into_inner();
+        if start > end {
+            return Vec::new();
+        }
+
+        // Elves cleaning the first section, and then the ones starting in the other sections.
+        let mut elves = self.stab(start);
+        let first = self
+            .by_start
+            .partition_point(|&elf| self.elf_ranges[elf].start <= start);
+        let last = self
+            .by_start
+            .partition_point(|&elf| self.elf_ranges[elf].start <= end);
+        elves.extend(&self.by_start[first..last]);
+        elves
+    }
+
+    /// Elves sharing a section with `elf`, or none if there is no such elf.
+    pub fn overlapping_with(&self, elf: usize) -> Vec<usize> {
+        let Some(range) = self.elf_ranges.get(elf) else {
+            return Vec::new();
+        };
+
+        let mut elves = self.overlapping(range.start..=range.end);
+        elves.retain(|&other| other != elf);
+        elves
+    }
+
+    /// Number of pairs of elves, from anywhere in the input, sharing at least one section.
+    ///
+    /// Counts the pairs where one elf ends before the other starts with a sweep over the sorted
+    /// ends, and takes them from the number of pairs, so it is `O(n log n)` however many overlap.
+    pub fn overlapping_pair_count(&self) -> u64 {
+        let disjoint = self
+            .elf_ranges
+            .iter()
+            .map(|range| self.ends.partition_point(|&end| end < range.start) as u64)
+            .sum::<u64>();
+        let elves = self.elf_ranges.len() as u64;
+
+        elves * elves.saturating_sub(1) / 2 - disjoint
+    }
+}
+
+impl SectionAssignments {
+    pub fn index(&self) -> SectionIndex {
+        SectionIndex::new(
+            self.assignments
+                .iter()
+                .flat_map(|pair| [pair.left, pair.right])
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut elves: Vec<usize>) -> Vec<usize> {
+        elves.sort_unstable();
+        elves
+    }
+
+    #[test]
+    fn it_finds_the_elves_overlapping_across_pairs() {
+        let assignments = SectionAssignments::try_from("2-4,6-8\n2-3,4-5\n5-7,7-9").unwrap();
+
+        let index = assignments.index();
+
+        assert_eq!(index.len(), 6);
+        assert_eq!(sorted(index.stab(4)), vec![0, 3]);
+        assert_eq!(sorted(index.stab(10)), Vec::<usize>::new());
+        assert_eq!(sorted(index.overlapping(7..=7)), vec![1, 4, 5]);
+        assert_eq!(sorted(index.overlapping_with(0)), vec![2, 3]);
+        assert_eq!(sorted(index.overlapping_with(4)), vec![1, 3, 5]);
+        assert_eq!(sorted(index.overlapping_with(6)), Vec::<usize>::new());
+        assert_eq!(index.overlapping_pair_count(), 6);
+    }
+
+    #[test]
+    fn it_answers_like_checking_every_pair() {
+        let mut state = 0x9E37_79B9u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        let ranges = (0..500)
+            .map(|_| {
+                let start = next() % 1000;
+                Range {
+                    start,
+                    end: start + next() % 50,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let index = SectionIndex::new(ranges.clone());
+
+        let mut pairs = 0;
+        for (elf, range) in ranges.iter().enumerate() {
+            let expected = (0..ranges.len())
+                .filter(|&other| other != elf && ranges[other].overlaps(range))
+                .collect::<Vec<_>>();
+            pairs += expected.len() as u64;
+
+            assert_eq!(sorted(index.overlapping_with(elf)), expected);
+        }
+        for section in (0..1100).step_by(7) {
+            let expected = (0..ranges.len())
+                .filter(|&elf| ranges[elf].start <= section && section <= ranges[elf].end)
+                .collect::<Vec<_>>();
+
+            assert_eq!(sorted(index.stab(section)), expected);
+        }
+        assert_eq!(index.overlapping_pair_count(), pairs / 2);
+    }
+}
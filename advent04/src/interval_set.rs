@@ -0,0 +1,204 @@
+use crate::Range;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// Set of sections, stored as the sorted ranges left after merging every overlapping or adjacent
+/// range that was added to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Range>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    /// Adds the sections of the range, which adds nothing if the range is empty.
+    pub fn insert(&mut self, sections: RangeInclusive<u32>) {
+        let (start, end) = sections.into_inner();
+        self.insert_range(Range { start, end });
+    }
+
+    pub(crate) fn insert_range(&mut self, range: Range) {
+        if range.start > range.end {
+            return;
+        }
+
+        let first = self
+            .ranges
+            .partition_point(|other| other.end.saturating_add(1) < range.start);
+        let last = self
+            .ranges
+            .partition_point(|other| other.start <= range.end.saturating_add(1));
+
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold(range, |merged, other| Range {
+                start: merged.start.min(other.start),
+                end: merged.end.max(other.end),
+            });
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        let index = self.ranges.partition_point(|range| range.end < section);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= section)
+    }
+
+    /// Number of sections in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| u64::from(range.end - range.start) + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The merged ranges of the set, from the lowest section to the highest.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<u32>> + '_ {
+        self.ranges.iter().map(|range| range.start..=range.end)
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert_range(*range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(left), Some(right)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = left.start.max(right.start);
+            let end = left.end.min(right.end);
+            if start <= end {
+                ranges.push(Range { start, end });
+            }
+
+            if left.end < right.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Sections of the set that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+
+        for range in &self.ranges {
+            let first = other.ranges.partition_point(|hole| hole.end < range.start);
+            let holes = other.ranges[first..]
+                .iter()
+                .take_while(|hole| hole.start <= range.end);
+
+            let mut start = Some(range.start);
+            for hole in holes {
+                let Some(from) = start else { break };
+                if hole.start > from {
+                    ranges.push(Range {
+                        start: from,
+                        end: hole.start - 1,
+                    });
+                }
+                start = if hole.end < range.end {
+                    Some(hole.end + 1)
+                } else {
+                    None
+                };
+            }
+
+            if let Some(start) = start {
+                ranges.push(Range {
+                    start,
+                    end: range.end,
+                });
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Sections between the lowest and the highest section of the set that are not in it.
+    pub fn gaps(&self) -> IntervalSet {
+        let ranges = self
+            .ranges
+            .windows(2)
+            .map(|pair| Range {
+                start: pair[0].end + 1,
+                end: pair[1].start - 1,
+            })
+            .collect();
+
+        IntervalSet { ranges }
+    }
+}
+
+impl FromIterator<RangeInclusive<u32>> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = RangeInclusive<u32>>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for sections in iter {
+            set.insert(sections);
+        }
+        set
+    }
+}
+
+/// Ranges separated by commas, like the assignments of a pair of elves.
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}-{}", range.start, range.end)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_merges_overlapping_and_adjacent_ranges() {
+        let set = IntervalSet::from_iter([6..=8, 2..=4, 10..=12, 5..=5, 7..=11, 20..=21]);
+
+        assert_eq!(set.to_string(), "2-12,20-21");
+        assert_eq!(set.len(), 13);
+        assert!(set.contains(2));
+        assert!(set.contains(21));
+        assert!(!set.contains(1));
+        assert!(!set.contains(15));
+        assert_eq!(set.gaps().to_string(), "13-19");
+    }
+
+    #[test]
+    fn it_combines_interval_sets() {
+        let left = IntervalSet::from_iter([1..=5, 10..=15, 20..=25]);
+        let right = IntervalSet::from_iter([4..=11, 15..=15, 17..=30]);
+
+        assert_eq!(left.union(&right).to_string(), "1-15,17-30");
+        assert_eq!(
+            left.intersection(&right).to_string(),
+            "4-5,10-11,15-15,20-25"
+        );
+        assert_eq!(left.difference(&right).to_string(), "1-3,12-14");
+        assert_eq!(right.difference(&left).to_string(), "6-9,17-19,26-30");
+        assert_eq!(left.difference(&left), IntervalSet::new());
+        assert!(IntervalSet::new().gaps().is_empty());
+    }
+}
@@ -1,77 +1,255 @@
 #![allow(unused)]
 
-use std::num::ParseIntError;
+mod index;
+mod interval_set;
+
+use advent::{Location, Solution, Span};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingEnd,
+    MissingRight,
+    InvalidSection(String),
+    NegativeSection(String),
+    ReversedRange { start: u32, end: u32 },
+    TooManyParts,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::MissingEnd => write!(f, "no end defined"),
+            ParseErrorKind::MissingRight => write!(f, "no right range defined"),
+            ParseErrorKind::InvalidSection(section) => {
+                write!(f, "section '{}' is not a number", section)
+            }
+            ParseErrorKind::NegativeSection(section) => {
+                write!(f, "section '{}' is negative", section)
+            }
+            ParseErrorKind::ReversedRange { start, end } => {
+                write!(f, "range starts at {} after it ends at {}", start, end)
+            }
+            ParseErrorKind::TooManyParts => write!(f, "too many parts"),
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+/// How forgiving the parser is with ranges that are not written as `start-end`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Only accepts `start-end` with `start` not after `end`.
+    #[default]
+    Strict,
+    /// Also accepts reversed ranges like `8-2`, which are read as `2-8`, ranges without end like
+    /// `5-`, which go up to the last section, and single sections like `7`.
+    Lenient,
+}
+
+pub use index::SectionIndex;
+pub use interval_set::IntervalSet;
 
-struct Range {
+/// Sections assigned to an elf, from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Range {
     start: u32,
     end: u32,
 }
 
+/// Parses a range in [`ParseMode::Strict`] mode.
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(input, ParseMode::Strict)
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
 impl Range {
-    fn from_str(input: &str) -> Result<Range, String> {
-        let mut parts = input.split('-');
-
-        let start = parts
-            .next()
-            .ok_or("No start defined")?
-            .parse()
-            .map_err(|e: ParseIntError| e.to_string())?;
-        let end = parts
-            .next()
-            .ok_or("No end defined")?
-            .parse()
-            .map_err(|e: ParseIntError| e.to_string())?;
-
-        if parts.next().is_some() {
-            return Err("Too many parts".to_string());
+    /// Range of the sections between `first` and `last`, which can be given in any order.
+    pub fn new(first: u32, last: u32) -> Range {
+        Range {
+            start: first.min(last),
+            end: first.max(last),
+        }
+    }
+
+    pub fn parse_with(input: &str, mode: ParseMode) -> Result<Range, ParseError> {
+        Self::from_str_in_line(input, input, mode)
+    }
+
+    fn from_str_in_line(input: &str, line: &str, mode: ParseMode) -> Result<Range, ParseError> {
+        let parts = Self::split_sections(input);
+        let end_of_input = &input[input.len()..];
+
+        let start = Self::section_from_str(parts[0], line)?;
+        let end = match (parts.get(1), mode) {
+            (None, ParseMode::Lenient) => start,
+            (None, ParseMode::Strict) => {
+                let kind = ParseErrorKind::MissingEnd;
+                return Err(ParseError::new(kind, Span::of(1, line, end_of_input)));
+            }
+            (Some(&""), ParseMode::Lenient) => u32::MAX,
+            (Some(end), _) => Self::section_from_str(end, line)?,
+        };
+
+        if let Some(part) = parts.get(2) {
+            let location = Span::of(1, line, part);
+            return Err(ParseError::new(ParseErrorKind::TooManyParts, location));
+        }
+
+        match mode {
+            ParseMode::Strict if start > end => {
+                let kind = ParseErrorKind::ReversedRange { start, end };
+                Err(ParseError::new(kind, Span::of(1, line, input)))
+            }
+            _ => Ok(Range::new(start, end)),
+        }
+    }
+
+    /// Parts of the range separated by `-`, where a `-` starting a part is the sign of a negative
+    /// section instead of a separator.
+    fn split_sections(input: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+
+        for (i, c) in input.char_indices() {
+            if c == '-' && i > start {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
         }
+        parts.push(&input[start..]);
+
+        parts
+    }
 
-        Ok(Range { start, end })
+    fn section_from_str(section: &str, line: &str) -> Result<u32, ParseError> {
+        section.parse().map_err(|_| {
+            let negative = section
+                .strip_prefix('-')
+                .is_some_and(|digits| digits.parse::<u32>().is_ok());
+            let kind = if negative {
+                ParseErrorKind::NegativeSection(section.to_string())
+            } else {
+                ParseErrorKind::InvalidSection(section.to_string())
+            };
+            ParseError::new(kind, Span::of(1, line, section))
+        })
     }
 
-    fn start(&self) -> u32 {
+    pub fn start(&self) -> u32 {
         self.start
     }
 
-    fn end(&self) -> u32 {
+    pub fn end(&self) -> u32 {
         self.end
     }
 
-    fn contains(&self, other: &Range) -> bool {
+    pub fn contains(&self, other: &Range) -> bool {
         self.start <= other.start && self.end >= other.end
     }
 
-    fn overlaps(&self, other: &Range) -> bool {
+    pub fn overlaps(&self, other: &Range) -> bool {
         self.start <= other.end && self.end >= other.start
     }
 }
 
-struct RangePair {
+/// How the ranges of a pair of elves relate to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overlap {
+    Disjoint,
+    /// The ranges share some sections, but neither contains the other.
+    Overlapping,
+    /// One range contains the other, which is shorter.
+    Contained,
+    Identical,
+}
+
+/// Ranges of a pair of elves, written as `left,right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangePair {
     left: Range,
     right: Range,
 }
 
-impl RangePair {
-    fn from_str(input: &str) -> Result<RangePair, String> {
-        let mut parts = input.split(',');
+/// Parses a pair in [`ParseMode::Strict`] mode.
+impl FromStr for RangePair {
+    type Err = ParseError;
 
-        let left = Range::from_str(parts.next().ok_or("No left defined")?)?;
-        let right = Range::from_str(parts.next().ok_or("No right defined")?)?;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(input, ParseMode::Strict)
+    }
+}
 
-        if parts.next().is_some() {
-            return Err("Too many parts".to_string());
+impl Display for RangePair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.left, self.right)
+    }
+}
+
+impl RangePair {
+    pub fn new(left: Range, right: Range) -> RangePair {
+        RangePair { left, right }
+    }
+
+    pub fn parse_with(input: &str, mode: ParseMode) -> Result<RangePair, ParseError> {
+        let mut parts = input.split(',');
+        let end_of_input = &input[input.len()..];
+
+        let left = Range::from_str_in_line(parts.next().unwrap_or(end_of_input), input, mode)?;
+        let right = parts.next().ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::MissingRight,
+                Span::of(1, input, end_of_input),
+            )
+        })?;
+        let right = Range::from_str_in_line(right, input, mode)?;
+
+        if let Some(part) = parts.next() {
+            let location = Span::of(1, input, part);
+            return Err(ParseError::new(ParseErrorKind::TooManyParts, location));
         }
 
         Ok(RangePair { left, right })
     }
 
-    fn is_fully_contained(&self) -> bool {
+    pub fn left(&self) -> Range {
+        self.left
+    }
+
+    pub fn right(&self) -> Range {
+        self.right
+    }
+
+    pub fn is_fully_contained(&self) -> bool {
         self.left.contains(&self.right) || self.right.contains(&self.left)
     }
 
-    fn overlaps(&self) -> bool {
+    pub fn overlaps(&self) -> bool {
         self.left.overlaps(&self.right)
     }
+
+    pub fn classify(&self) -> Overlap {
+        if self.left == self.right {
+            Overlap::Identical
+        } else if self.is_fully_contained() {
+            Overlap::Contained
+        } else if self.overlaps() {
+            Overlap::Overlapping
+        } else {
+            Overlap::Disjoint
+        }
+    }
 }
 
 pub struct SectionAssignments {
@@ -79,19 +257,29 @@ pub struct SectionAssignments {
 }
 
 impl TryFrom<&str> for SectionAssignments {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
+        SectionAssignments::parse_with(input, ParseMode::Strict)
+    }
+}
+
+impl SectionAssignments {
+    pub fn parse_with(input: &str, mode: ParseMode) -> Result<SectionAssignments, ParseError> {
         let assignments = input
             .lines()
-            .map(RangePair::from_str)
+            .enumerate()
+            .map(|(i, line)| RangePair::parse_with(line, mode).map_err(|e| e.with_line_offset(i)))
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(SectionAssignments { assignments })
     }
-}
 
-impl SectionAssignments {
+    /// Every pair of elves, in the order of the input, with how their ranges relate.
+    pub fn classified(&self) -> impl Iterator<Item = (&RangePair, Overlap)> + '_ {
+        self.assignments.iter().map(|pair| (pair, pair.classify()))
+    }
+
     pub fn fully_contained_count(&self) -> usize {
         self.assignments
             .iter()
@@ -105,6 +293,42 @@ impl SectionAssignments {
             .filter(|a| a.overlaps())
             .count()
     }
+
+    /// Sections assigned to at least one elf.
+    pub fn cleaned_sections(&self) -> IntervalSet {
+        let mut sections = IntervalSet::new();
+        for pair in &self.assignments {
+            sections.insert_range(pair.left);
+            sections.insert_range(pair.right);
+        }
+        sections
+    }
+
+    /// Sections between the lowest and the highest assigned section that nobody cleans.
+    pub fn uncleaned_sections(&self) -> IntervalSet {
+        self.cleaned_sections().gaps()
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = SectionAssignments;
+    type Error = ParseError;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        SectionAssignments::try_from(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(input.fully_contained_count())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(input.overlap_count())
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +385,155 @@ mod tests {
         assert_eq!(section_assignments.fully_contained_count(), 2);
     }
 
+    #[test]
+    fn it_reports_where_the_section_assignments_are_malformed() {
+        let errors = [
+            "2-4,6-8\n2-3,4-x",
+            "2-4,6-8\n2-3",
+            "2-4,6-8\n2-3,4",
+            "2-4,6-8\n2-3,4-5-6",
+        ]
+        .map(|input| SectionAssignments::try_from(input).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::InvalidSection("x".to_string()),
+                Location::new(2, 7)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(ParseErrorKind::MissingRight, Location::new(2, 4))
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(ParseErrorKind::MissingEnd, Location::new(2, 6))
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(ParseErrorKind::TooManyParts, Location::new(2, 9))
+        );
+    }
+
+    #[test]
+    fn it_rejects_reversed_and_negative_ranges_in_strict_mode() {
+        let errors = ["2-4,8-2", "2-4,-3-5", "2-4,3--5", "2-4,5-"]
+            .map(|input| SectionAssignments::try_from(input).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::ReversedRange { start: 8, end: 2 },
+                Span::new(1, 5, 3)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(
+                ParseErrorKind::NegativeSection("-3".to_string()),
+                Span::new(1, 5, 2)
+            )
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::NegativeSection("-5".to_string()),
+                Span::new(1, 7, 2)
+            )
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(
+                ParseErrorKind::InvalidSection("".to_string()),
+                Location::new(1, 7)
+            )
+        );
+    }
+
+    #[test]
+    fn it_normalises_ranges_in_lenient_mode() {
+        let range_pair = RangePair::parse_with("8-2,7", ParseMode::Lenient).unwrap();
+        let open_ended = RangePair::parse_with("5-,1-6", ParseMode::Lenient).unwrap();
+
+        assert_eq!(range_pair.left, Range { start: 2, end: 8 });
+        assert_eq!(range_pair.right, Range { start: 7, end: 7 });
+        assert!(range_pair.is_fully_contained());
+        assert_eq!(
+            open_ended.left,
+            Range {
+                start: 5,
+                end: u32::MAX
+            }
+        );
+        assert!(open_ended.overlaps());
+        assert!(!open_ended.is_fully_contained());
+        assert_eq!(
+            SectionAssignments::parse_with("2-4,-3", ParseMode::Lenient)
+                .err()
+                .unwrap()
+                .kind(),
+            &ParseErrorKind::NegativeSection("-3".to_string())
+        );
+    }
+
+    #[test]
+    fn it_writes_ranges_like_they_are_parsed() {
+        let range_pair = "12-40,7-7".parse::<RangePair>().unwrap();
+        let lenient = RangePair::parse_with("8-2,5", ParseMode::Lenient).unwrap();
+
+        assert_eq!(range_pair.to_string(), "12-40,7-7");
+        assert_eq!(range_pair.left(), Range::new(40, 12));
+        assert_eq!(lenient.to_string(), "2-8,5-5");
+        assert_eq!(lenient.to_string().parse::<RangePair>(), Ok(lenient));
+        assert_eq!(
+            Range::new(5, u32::MAX).to_string().parse::<Range>(),
+            Ok(Range::new(5, u32::MAX))
+        );
+    }
+
+    #[test]
+    fn it_classifies_every_pair_of_elves() {
+        let section_assignments = SectionAssignments::try_from(input()).unwrap();
+
+        let classified = section_assignments
+            .classified()
+            .map(|(pair, overlap)| (pair.to_string(), overlap))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            classified,
+            vec![
+                ("2-4,6-8".to_string(), Overlap::Disjoint),
+                ("2-3,4-5".to_string(), Overlap::Disjoint),
+                ("5-7,7-9".to_string(), Overlap::Overlapping),
+                ("2-8,3-7".to_string(), Overlap::Contained),
+                ("6-6,4-6".to_string(), Overlap::Contained),
+                ("2-6,4-8".to_string(), Overlap::Overlapping),
+            ]
+        );
+        assert_eq!(
+            "3-5,3-5".parse::<RangePair>().unwrap().classify(),
+            Overlap::Identical
+        );
+    }
+
+    #[test]
+    fn it_finds_the_sections_that_nobody_cleans() {
+        let section_assignments =
+            SectionAssignments::try_from("2-4,12-14\n3-5,20-22\n6-6,15-15").unwrap();
+
+        assert_eq!(
+            section_assignments.cleaned_sections().to_string(),
+            "2-6,12-15,20-22"
+        );
+        assert_eq!(
+            section_assignments.uncleaned_sections().to_string(),
+            "7-11,16-19"
+        );
+        assert_eq!(section_assignments.uncleaned_sections().len(), 9);
+    }
+
     fn input() -> &'static str {
         "2-4,6-8
 2-3,4-5
@@ -0,0 +1,186 @@
+use crate::{CraneError, Ship};
+
+/// How a crane moves crates from the top of one stack to the top of another.
+///
+/// Models decide the order the moved crates end up in, but every model has to follow the same
+/// contract, which the rest of the crate relies on:
+///
+/// - A move only changes the stacks `from` and `to`, which the [`Planner`](crate::Planner) relies
+///   on to estimate the moves left.
+/// - A move fails with a [`CraneError`] when either stack does not exist or `from` has less than
+///   `count` crates. It may leave the ship partly changed then, since instructions are always
+///   executed on a copy of the ship, which is only kept when they succeed.
+pub trait CraneModel {
+    fn name(&self) -> &str;
+
+    /// Moves `count` crates from stack `from` to stack `to`, where stacks are numbered from 1.
+    fn move_crates(
+        &self,
+        ship: &mut Ship,
+        count: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), CraneError>;
+}
+
+/// Moves one crate at a time, so the moved crates end up in reverse order.
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn name(&self) -> &str {
+        "CrateMover 9000"
+    }
+
+    fn move_crates(
+        &self,
+        ship: &mut Ship,
+        count: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), CraneError> {
+        for _ in 0..count {
+            let crates = ship.take(1, from, to)?;
+            ship.put(crates, to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Moves all the crates at once, so they keep their order.
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn name(&self) -> &str {
+        "CrateMover 9001"
+    }
+
+    fn move_crates(
+        &self,
+        ship: &mut Ship,
+        count: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), CraneError> {
+        let crates = ship.take(count, from, to)?;
+        ship.put(crates, to)
+    }
+}
+
+/// Moves at most `capacity` crates at once, starting with the top ones, so every batch keeps its
+/// order but the batches end up in reverse order.
+pub struct LiftCapacity {
+    capacity: usize,
+}
+
+impl LiftCapacity {
+    /// Crane lifting `capacity` crates at once, or a single one if `capacity` is 0.
+    pub fn new(capacity: usize) -> LiftCapacity {
+        LiftCapacity {
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+impl CraneModel for LiftCapacity {
+    fn name(&self) -> &str {
+        "lift capacity"
+    }
+
+    fn move_crates(
+        &self,
+        ship: &mut Ship,
+        count: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), CraneError> {
+        let mut remaining = count;
+        while remaining > 0 {
+            let batch = remaining.min(self.capacity);
+            let crates = ship.take(batch, from, to)?;
+            ship.put(crates, to)?;
+            remaining -= batch;
+        }
+        Ok(())
+    }
+}
+
+/// Moves all the crates at once, but swaps every two crates, counting from the bottom ones. An
+/// odd crate left at the top keeps its place.
+pub struct PairFlipper;
+
+impl CraneModel for PairFlipper {
+    fn name(&self) -> &str {
+        "pair flipper"
+    }
+
+    fn move_crates(
+        &self,
+        ship: &mut Ship,
+        count: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), CraneError> {
+        let mut crates = ship.take(count, from, to)?;
+        for pair in crates.chunks_exact_mut(2) {
+            pair.swap(0, 1);
+        }
+        ship.put(crates, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ship() -> Ship {
+        Ship::try_from("[E]\n[D]\n[C]\n[B]\n[A]\n 1   2").unwrap()
+    }
+
+    #[test]
+    fn it_moves_crates_in_batches_up_to_the_lift_capacity() {
+        let mut ship = ship();
+
+        LiftCapacity::new(2)
+            .move_crates(&mut ship, 5, 1, 2)
+            .unwrap();
+
+        assert_eq!(ship.stacks()[1], ["D", "E", "B", "C", "A"]);
+    }
+
+    #[test]
+    fn it_flips_every_pair_of_moved_crates() {
+        let mut ship = ship();
+
+        PairFlipper.move_crates(&mut ship, 5, 1, 2).unwrap();
+
+        assert_eq!(ship.stacks()[1], ["B", "A", "D", "C", "E"]);
+    }
+
+    #[test]
+    fn it_checks_the_stacks_before_moving_crates() {
+        let models: [&dyn CraneModel; 4] = [
+            &CrateMover9000,
+            &CrateMover9001,
+            &LiftCapacity::new(2),
+            &PairFlipper,
+        ];
+
+        for model in models {
+            let mut ship = ship();
+
+            assert_eq!(
+                model.move_crates(&mut ship, 1, 1, 3),
+                Err(CraneError::NoSuchStack(3)),
+                "{}",
+                model.name()
+            );
+            assert_eq!(
+                model.move_crates(&mut ship, 1, 0, 2),
+                Err(CraneError::NoSuchStack(0)),
+                "{}",
+                model.name()
+            );
+            assert_eq!(ship.stacks()[0].len(), 5, "{}", model.name());
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{CargoCrane, CraneModel, Instruction, InstructionError, Ship};
+use std::collections::VecDeque;
+
+/// Execution of the instructions of a [`CargoCrane`] one at a time, which keeps what every
+/// instruction changed so it can go back to any of them.
+pub struct Execution<'a> {
+    crane: &'a CargoCrane,
+    model: &'a dyn CraneModel,
+    ship: Ship,
+    /// Changes of every executed instruction, in order.
+    changes: Vec<Vec<Change>>,
+}
+
+/// Change of an instruction to a stack, which kept its `kept` lowest crates and replaced the
+/// crates above them, which were `replaced`.
+struct Change {
+    stack: usize,
+    kept: usize,
+    replaced: VecDeque<String>,
+}
+
+impl Instruction {
+    /// Stacks the instruction changes, which are the only ones a crane model may change.
+    fn stacks(&self) -> Vec<usize> {
+        match *self {
+            Instruction::Move { from, to, .. } if from != to => vec![from, to],
+            Instruction::Swap { first, second } if first != second => vec![first, second],
+            Instruction::Move { from: stack, .. }
+            | Instruction::Swap { first: stack, .. }
+            | Instruction::Reverse { stack }
+            | Instruction::Rotate { stack, .. }
+            | Instruction::Insert { stack, .. }
+            | Instruction::Remove { stack } => vec![stack],
+        }
+    }
+}
+
+impl<'a> Execution<'a> {
+    pub(crate) fn new(crane: &'a CargoCrane, model: &'a dyn CraneModel) -> Execution<'a> {
+        Execution {
+            crane,
+            model,
+            ship: crane.ship.clone(),
+            changes: Vec::new(),
+        }
+    }
+
+    /// The ship after the executed instructions.
+    pub fn ship(&self) -> &Ship {
+        &self.ship
+    }
+
+    /// Number of instructions executed.
+    pub fn position(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position() == self.crane.instructions.len()
+    }
+
+    /// Executes the next instruction, returning `false` if there are none left. The ship is left
+    /// as it was if the instruction fails.
+    pub fn step(&mut self) -> Result<bool, InstructionError> {
+        let index = self.position();
+        let Some(instruction) = self.crane.instructions.get(index) else {
+            return Ok(false);
+        };
+
+        let before = instruction
+            .stacks()
+            .into_iter()
+            .filter_map(|stack| {
+                let crates = self.ship.stacks.get(stack.checked_sub(1)?)?;
+                Some((stack, crates.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(error) = self.ship.execute(instruction, self.model) {
+            for (stack, crates) in before {
+                self.ship.stacks[stack - 1] = crates;
+            }
+            return Err(self.crane.instruction_error(index, error));
+        }
+
+        let changes = before
+            .into_iter()
+            .map(|(stack, mut crates)| {
+                let after = &self.ship.stacks[stack - 1];
+                let kept = crates.iter().zip(after).take_while(|(a, b)| a == b).count();
+                Change {
+                    stack,
+                    kept,
+                    replaced: crates.split_off(kept),
+                }
+            })
+            .collect();
+        self.changes.push(changes);
+        Ok(true)
+    }
+
+    /// Goes back to the ship before the last executed instruction, returning `false` if there is
+    /// none.
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.changes.pop() else {
+            return false;
+        };
+
+        for change in changes {
+            let crates = &mut self.ship.stacks[change.stack - 1];
+            crates.truncate(change.kept);
+            crates.extend(change.replaced);
+        }
+        true
+    }
+
+    /// Goes to the ship after the first `position` instructions, or after all of them if there
+    /// are fewer. When going forward stops at a failing instruction, the ship is the one before it.
+    pub fn seek(&mut self, position: usize) -> Result<(), InstructionError> {
+        let position = position.min(self.crane.instructions.len());
+
+        while self.position() > position {
+            self.undo();
+        }
+        while self.position() < position {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Executes every instruction left.
+    pub fn run(&mut self) -> Result<(), InstructionError> {
+        self.seek(self.crane.instructions.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CraneError, CrateMover9000, PairFlipper};
+    use advent::Span;
+
+    fn cargo_crane() -> CargoCrane {
+        CargoCrane::try_from(
+            "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_steps_through_the_instructions_and_back() {
+        let cargo_crane = cargo_crane();
+        let mut execution = cargo_crane.execution(&CrateMover9000);
+
+        assert!(execution.step().unwrap());
+        assert_eq!(execution.ship().crates_message_to_elves(), "DCP");
+        assert!(execution.step().unwrap());
+        assert_eq!(execution.ship().crates_message_to_elves(), "CZ");
+        assert_eq!(execution.position(), 2);
+
+        assert!(execution.undo());
+        assert_eq!(execution.ship().crates_message_to_elves(), "DCP");
+        assert!(execution.undo());
+        assert!(!execution.undo());
+        assert_eq!(execution.ship(), cargo_crane.ship());
+    }
+
+    #[test]
+    fn it_seeks_any_instruction() {
+        let cargo_crane = cargo_crane();
+        let mut execution = cargo_crane.execution(&CrateMover9000);
+
+        execution.seek(10).unwrap();
+        assert!(execution.is_finished());
+        assert_eq!(execution.ship().crates_message_to_elves(), "CMZ");
+        assert!(!execution.step().unwrap());
+
+        execution.seek(1).unwrap();
+        assert_eq!(execution.position(), 1);
+        assert_eq!(execution.ship().crates_message_to_elves(), "DCP");
+
+        execution.run().unwrap();
+        assert_eq!(execution.ship().crates_message_to_elves(), "CMZ");
+    }
+
+    #[test]
+    fn it_keeps_the_ship_before_the_failing_instruction() {
+        let cargo_crane =
+            CargoCrane::try_from("[A]\n 1   2\n\nmove 1 from 1 to 2\nmove 2 from 2 to 1").unwrap();
+        let mut execution = cargo_crane.execution(&CrateMover9000);
+
+        let result = execution.run();
+
+        assert_eq!(
+            result,
+            Err(InstructionError {
+                index: 1,
+                span: Span::new(5, 1, 18),
+                error: CraneError::EmptyStack(2)
+            })
+        );
+        assert_eq!(execution.position(), 1);
+        assert_eq!(execution.ship().crates_message_to_elves(), "A");
+    }
+
+    #[test]
+    fn it_undoes_every_kind_of_instruction() {
+        let cargo_crane = CargoCrane::try_from(
+            "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 3 from 2 to 1
+swap 1 and 3
+rotate 3 on 3
+reverse 3
+insert E on 2
+remove from 1
+swap 2 and 2",
+        )
+        .unwrap();
+        let mut execution = cargo_crane.execution(&PairFlipper);
+
+        let mut ships = vec![execution.ship().clone()];
+        while execution.step().unwrap() {
+            ships.push(execution.ship().clone());
+        }
+
+        assert_eq!(ships[7].crates_message_to_elves(), "EZ");
+        for position in (0..ships.len()).rev() {
+            execution.seek(position).unwrap();
+            assert_eq!(execution.ship(), &ships[position], "{}", position);
+        }
+        assert!(!execution.undo());
+    }
+}
@@ -1,13 +1,217 @@
 #![allow(dead_code)]
 
+mod crane;
+mod execution;
+mod planner;
+
+use advent::{Diagnostic, Location, Solution, Span};
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingStackIds,
+    InvalidStackId(String),
+    UnexpectedStackId {
+        expected: usize,
+        found: usize,
+    },
+    UnclosedCrate,
+    EmptyCrate,
+    NotACrate(String),
+    MisalignedCrate(String),
+    MissingInstruction,
+    UnknownInstruction(String),
+    MissingArgument(&'static str),
+    InvalidArgument(&'static str, String),
+    /// The keyword the instruction expects, and the word found instead.
+    UnexpectedWord(&'static str, String),
+    TrailingWord(String),
+    InvalidLabel(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::MissingStackIds => write!(f, "no line with the stack ids"),
+            ParseErrorKind::InvalidStackId(id) => write!(f, "stack id '{}' is not a number", id),
+            ParseErrorKind::UnexpectedStackId { expected, found } => {
+                write!(f, "expected stack id {}, found {}", expected, found)
+            }
+            ParseErrorKind::UnclosedCrate => write!(f, "crate is not closed with ']'"),
+            ParseErrorKind::EmptyCrate => write!(f, "crate has no label"),
+            ParseErrorKind::NotACrate(text) => write!(f, "'{}' is not a crate like '[A]'", text),
+            ParseErrorKind::MisalignedCrate(label) => {
+                write!(f, "crate '{}' is not above exactly one stack id", label)
+            }
+            ParseErrorKind::MissingInstruction => write!(f, "no instruction"),
+            ParseErrorKind::UnknownInstruction(instruction) => {
+                write!(f, "unknown instruction '{}'", instruction)
+            }
+            ParseErrorKind::MissingArgument(argument) => {
+                write!(f, "no {} for the instruction", argument)
+            }
+            ParseErrorKind::InvalidArgument(argument, value) => {
+                write!(f, "{} '{}' is not a number", argument, value)
+            }
+            ParseErrorKind::UnexpectedWord(expected, found) => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseErrorKind::TrailingWord(word) => {
+                write!(f, "unexpected '{}' after the instruction", word)
+            }
+            ParseErrorKind::InvalidLabel(label) => {
+                write!(f, "crate label '{}' cannot have square brackets", label)
+            }
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+pub use crane::{CraneModel, CrateMover9000, CrateMover9001, LiftCapacity, PairFlipper};
+pub use execution::Execution;
+pub use planner::{Goal, Move, PlanError, Planner, DEFAULT_BUDGET};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraneError {
+    NoSuchStack(usize),
+    EmptyStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+}
+
+impl Display for CraneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CraneError::NoSuchStack(stack) => write!(f, "there is no stack {}", stack),
+            CraneError::EmptyStack(stack) => write!(f, "no crate to move in stack {}", stack),
+            CraneError::NotEnoughCrates {
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "{} crates requested from stack {}, which has {}",
+                requested, stack, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CraneError {}
+
+impl CraneError {
+    /// Error for taking `requested` crates from a stack that has fewer.
+    fn shortage(stack: usize, requested: usize, available: usize) -> CraneError {
+        match available {
+            0 => CraneError::EmptyStack(stack),
+            _ => CraneError::NotEnoughCrates {
+                stack,
+                requested,
+                available,
+            },
+        }
+    }
+}
 
+/// Error of the instruction at `index`, counting from 0, which is written in `span`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionError {
+    pub index: usize,
+    pub span: Span,
+    pub error: CraneError,
+}
+
+impl InstructionError {
+    pub fn line(&self) -> usize {
+        self.span.start.line
+    }
+}
+
+impl Display for InstructionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "instruction {} at line {} failed: {}",
+            self.index + 1,
+            self.line(),
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for InstructionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Crane(CraneError),
+    Instruction(InstructionError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Crane(e) => write!(f, "{}", e),
+            Error::Instruction(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Crane(e) => Some(e),
+            Error::Instruction(e) => Some(e),
+        }
+    }
+}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::Parse(e) => Some(e.span()),
+            Error::Crane(_) => None,
+            Error::Instruction(e) => Some(e.span),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<CraneError> for Error {
+    fn from(e: CraneError) -> Self {
+        Error::Crane(e)
+    }
+}
+
+impl From<InstructionError> for Error {
+    fn from(e: InstructionError) -> Self {
+        Error::Instruction(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Ship {
-    stacks: Vec<VecDeque<char>>,
+    stacks: Vec<VecDeque<String>>,
 }
 
 impl TryFrom<&str> for Ship {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let stacks = Self::stacks_from_str(value)?;
@@ -15,56 +219,286 @@ impl TryFrom<&str> for Ship {
     }
 }
 
+/// Drawing of the stacks like the one in the input, where every stack is as wide as its widest
+/// crate or id, and at least as wide as a crate with a single letter, and crates and ids are
+/// centered in it.
+impl Display for Ship {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ids = (1..=self.stacks.len())
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>();
+        let widths = self
+            .stacks
+            .iter()
+            .zip(&ids)
+            .map(|(stack, id)| {
+                stack
+                    .iter()
+                    .map(|label| label.chars().count() + 2)
+                    .fold(id.len().max(3), usize::max)
+            })
+            .collect::<Vec<_>>();
+        let height = self.stacks.iter().map(VecDeque::len).max().unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let line = self
+                .stacks
+                .iter()
+                .zip(&widths)
+                .map(|(stack, &width)| {
+                    let label = stack.get(level).map(|label| format!("[{}]", label));
+                    centered(label.as_deref().unwrap_or(""), width)
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let ids = ids
+            .iter()
+            .zip(&widths)
+            .map(|(id, &width)| centered(id, width))
+            .collect::<Vec<_>>();
+        write!(f, "{}", ids.join(" "))
+    }
+}
+
+fn centered(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.chars().count());
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(padding - left))
+}
+
+/// Token of a line of the drawing, with the columns of its first and last character.
+struct Token<'a> {
+    text: &'a str,
+    first: usize,
+    last: usize,
+}
+
 impl Ship {
-    fn stacks(&self) -> &Vec<VecDeque<char>> {
+    fn stacks(&self) -> &Vec<VecDeque<String>> {
         &self.stacks
     }
 
     pub fn crates_message_to_elves(&self) -> String {
         let mut message = String::new();
         for stack in &self.stacks {
-            if let Some(&c) = stack.back() {
-                message.push(c);
+            if let Some(label) = stack.back() {
+                message.push_str(label);
             }
         }
         message
     }
 
-    fn number_of_stacks_from_str(input: &str) -> Result<usize, String> {
-        let first_line_without_square_brackets = input
+    fn stack_mut(&mut self, stack: usize) -> Result<&mut VecDeque<String>, CraneError> {
+        stack
+            .checked_sub(1)
+            .and_then(|index| self.stacks.get_mut(index))
+            .ok_or(CraneError::NoSuchStack(stack))
+    }
+
+    /// Takes the top `count` crates of the stack, from the lowest to the top one, to put them on
+    /// stack `to`, which is checked first so no crate is taken when it does not exist.
+    pub(crate) fn take(
+        &mut self,
+        count: usize,
+        stack: usize,
+        to: usize,
+    ) -> Result<Vec<String>, CraneError> {
+        self.stack_mut(to)?;
+        let crates = self.stack_mut(stack)?;
+        let available = crates.len();
+        if count > available {
+            return Err(CraneError::shortage(stack, count, available));
+        }
+
+        Ok(crates.drain(available - count..).collect())
+    }
+
+    /// Puts the crates on top of the stack, from the lowest to the top one.
+    pub(crate) fn put(&mut self, crates: Vec<String>, stack: usize) -> Result<(), CraneError> {
+        self.stack_mut(stack)?.extend(crates);
+        Ok(())
+    }
+
+    fn swap(&mut self, first: usize, second: usize) -> Result<(), CraneError> {
+        self.stack_mut(first)?;
+        self.stack_mut(second)?;
+        self.stacks.swap(first - 1, second - 1);
+        Ok(())
+    }
+
+    fn reverse(&mut self, stack: usize) -> Result<(), CraneError> {
+        self.stack_mut(stack)?.make_contiguous().reverse();
+        Ok(())
+    }
+
+    fn rotate(&mut self, count: usize, stack: usize) -> Result<(), CraneError> {
+        let crates = self.stack_mut(stack)?;
+        let available = crates.len();
+        if count > available {
+            return Err(CraneError::NotEnoughCrates {
+                stack,
+                requested: count,
+                available,
+            });
+        }
+
+        if count > 0 {
+            crates.make_contiguous()[available - count..].rotate_right(1);
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, label: &str, stack: usize) -> Result<(), CraneError> {
+        self.stack_mut(stack)?.push_back(label.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, stack: usize) -> Result<(), CraneError> {
+        self.stack_mut(stack)?
+            .pop_back()
+            .ok_or(CraneError::EmptyStack(stack))?;
+        Ok(())
+    }
+
+    /// Executes the instruction, where only moves depend on the crane model.
+    fn execute(
+        &mut self,
+        instruction: &Instruction,
+        model: &dyn CraneModel,
+    ) -> Result<(), CraneError> {
+        match instruction {
+            Instruction::Move { count, from, to } => model.move_crates(self, *count, *from, *to),
+            Instruction::Swap { first, second } => self.swap(*first, *second),
+            Instruction::Reverse { stack } => self.reverse(*stack),
+            Instruction::Rotate { count, stack } => self.rotate(*count, *stack),
+            Instruction::Insert { label, stack } => self.insert(label, *stack),
+            Instruction::Remove { stack } => self.remove(*stack),
+        }
+    }
+
+    /// Index of the line with the stack ids, which is the first one without crates, and the ids
+    /// found in it, which must go from 1 up to the number of stacks.
+    fn stack_ids_from_str(input: &str) -> Result<(usize, Vec<Token<'_>>), ParseError> {
+        let (line_index, line) = input
             .lines()
-            .find(|line| !line.contains('['))
-            .ok_or("No first line without square brackets")?;
-
-        let last_stack_id = first_line_without_square_brackets
-            .trim()
-            .split(' ')
-            .last()
-            .ok_or("No last stack id")?;
-
-        let last_stack_id_as_u32 = last_stack_id
-            .parse::<usize>()
-            .map_err(|_| format!("Last stack id '{}' is not a number", last_stack_id))?;
-
-        Ok(last_stack_id_as_u32)
-    }
-
-    fn stacks_from_str(input: &str) -> Result<Vec<VecDeque<char>>, String> {
-        let number_of_stacks = Self::number_of_stacks_from_str(input)?;
-        let mut stacks = Vec::with_capacity(number_of_stacks);
-        stacks.resize_with(number_of_stacks, VecDeque::new);
-
-        let lines_with_crates = input.lines().filter(|line| line.contains('['));
-        for line in lines_with_crates {
-            for (i, stack) in stacks.iter_mut().enumerate() {
-                let slice = (i * 4)..=(i * 4 + 2);
-                let crate_str = line.get(slice).unwrap_or("  ");
-                let maybe_crate_without_square_brackets =
-                    crate_str.replace(['[', ']'], "").trim().chars().next();
-
-                if let Some(crate_char) = maybe_crate_without_square_brackets {
-                    if crate_char != ' ' {
-                        stack.push_front(crate_char);
+            .enumerate()
+            .find(|(_, line)| !line.contains('['))
+            .ok_or_else(|| {
+                let line = input.lines().count().max(1);
+                ParseError::new(ParseErrorKind::MissingStackIds, Location::new(line, 1))
+            })?;
+
+        let ids = Self::words(line);
+        if ids.is_empty() {
+            let kind = ParseErrorKind::MissingStackIds;
+            return Err(ParseError::new(kind, Location::new(line_index + 1, 1)));
+        }
+
+        for (expected, id) in (1..).zip(&ids) {
+            let found = id.text.parse::<usize>().map_err(|_| {
+                let kind = ParseErrorKind::InvalidStackId(id.text.to_string());
+                ParseError::new(kind, Span::of(line_index + 1, line, id.text))
+            })?;
+            if found != expected {
+                let kind = ParseErrorKind::UnexpectedStackId { expected, found };
+                let span = Span::of(line_index + 1, line, id.text);
+                return Err(ParseError::new(kind, span));
+            }
+        }
+
+        Ok((line_index, ids))
+    }
+
+    /// Words of the line separated by spaces.
+    fn words(line: &str) -> Vec<Token<'_>> {
+        let mut words = Vec::new();
+        let mut start = None;
+
+        for (column, (i, c)) in line.char_indices().chain([(line.len(), ' ')]).enumerate() {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some((column, i)),
+                (Some((first, byte)), true) => {
+                    words.push(Token {
+                        text: &line[byte..i],
+                        first,
+                        last: column - 1,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        words
+    }
+
+    /// Crates of a line of the drawing, like `[A]`, separated by any number of spaces.
+    fn crates_from_str(line: &str) -> Result<Vec<Token<'_>>, ParseError> {
+        let mut crates = Vec::new();
+        let mut chars = line.char_indices().enumerate();
+
+        while let Some((first, (start, c))) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if c != '[' {
+                let end = line[start..]
+                    .find(|c: char| c.is_whitespace() || c == '[')
+                    .map_or(line.len(), |end| start + end);
+                let text = &line[start..end];
+                let kind = ParseErrorKind::NotACrate(text.to_string());
+                return Err(ParseError::new(kind, Span::of(1, line, text)));
+            }
+
+            let (last, end) = chars
+                .by_ref()
+                .find(|(_, (_, c))| matches!(c, '[' | ']'))
+                .filter(|(_, (_, c))| *c == ']')
+                .map(|(last, (end, _))| (last, end))
+                .ok_or_else(|| {
+                    let kind = ParseErrorKind::UnclosedCrate;
+                    ParseError::new(kind, Span::of(1, line, &line[start..start + 1]))
+                })?;
+
+            let text = &line[start + 1..end];
+            if text.is_empty() {
+                let kind = ParseErrorKind::EmptyCrate;
+                return Err(ParseError::new(kind, Span::of(1, line, &line[start..=end])));
+            }
+            crates.push(Token { text, first, last });
+        }
+
+        Ok(crates)
+    }
+
+    /// Stacks of the drawing, where every crate goes to the stack whose id is below it.
+    fn stacks_from_str(input: &str) -> Result<Vec<VecDeque<String>>, ParseError> {
+        let (ids_line, ids) = Self::stack_ids_from_str(input)?;
+        let mut stacks = vec![VecDeque::new(); ids.len()];
+
+        for (i, line) in input.lines().take(ids_line).enumerate() {
+            let crates = Self::crates_from_str(line).map_err(|e| e.with_line_offset(i))?;
+            let mut filled = vec![false; ids.len()];
+
+            for crate_token in crates {
+                let mut below = ids
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, id)| id.first <= crate_token.last && id.last >= crate_token.first)
+                    .map(|(stack, _)| stack);
+
+                match (below.next(), below.next()) {
+                    (Some(stack), None) if !filled[stack] => {
+                        filled[stack] = true;
+                        stacks[stack].push_front(crate_token.text.to_string());
+                    }
+                    _ => {
+                        let kind = ParseErrorKind::MisalignedCrate(crate_token.text.to_string());
+                        let span = Span::of(i + 1, line, crate_token.text);
+                        return Err(ParseError::new(kind, span));
                     }
                 }
             }
@@ -74,66 +508,175 @@ impl Ship {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum Instruction {
     Move {
         count: usize,
         from: usize,
         to: usize,
     },
+    /// Exchanges every crate of two stacks.
+    Swap { first: usize, second: usize },
+    /// Turns a stack upside down.
+    Reverse { stack: usize },
+    /// Moves the top crate under the rest of the top `count` crates of a stack.
+    Rotate { count: usize, stack: usize },
+    /// Puts a new crate on top of a stack.
+    Insert { label: String, stack: usize },
+    /// Takes the top crate of a stack off the ship.
+    Remove { stack: usize },
 }
 
-fn instructions_from_str(input: &str) -> Result<Vec<Instruction>, String> {
-    let lines = input.lines();
+fn instructions_from_str(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let instructions = sourced_instructions_from_str(input)?;
+    Ok(instructions
+        .into_iter()
+        .map(|(instruction, _)| instruction)
+        .collect())
+}
 
-    let lines_without_crates = lines
-        .filter(|line| !line.trim().is_empty())
-        .filter(|line| !line.contains(['[', ']']))
-        .skip(1); // Skip first line, which are the IDs
+/// Instructions of the input, with the span of the line where each of them is written. They are
+/// every non-blank line after the stack ids, whatever they contain.
+fn sourced_instructions_from_str(input: &str) -> Result<Vec<(Instruction, Span)>, ParseError> {
+    let (ids_line, _) = Ship::stack_ids_from_str(input)?;
 
-    lines_without_crates
-        .map(instruction_from_str)
+    input
+        .lines()
+        .enumerate()
+        .skip(ids_line + 1)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let instruction = instruction_from_str(line).map_err(|e| e.with_line_offset(i))?;
+            Ok((instruction, Span::new(i + 1, 1, line.chars().count())))
+        })
         .collect::<Result<Vec<_>, _>>()
 }
 
-fn instruction_from_str(line: &str) -> Result<Instruction, String> {
+fn instruction_from_str(line: &str) -> Result<Instruction, ParseError> {
     let mut words = line.split(' ');
-    let instruction = words.next().ok_or("No instruction")?;
+    let instruction = words
+        .next()
+        .filter(|word| !word.is_empty())
+        .ok_or_else(|| ParseError::new(ParseErrorKind::MissingInstruction, Location::new(1, 1)))?;
 
-    match instruction {
+    let instruction = match instruction {
         "move" => {
-            let mut words = words.step_by(2);
-            let count = words.next().ok_or("No count for move instruction")?;
-            let count = count
-                .parse::<usize>()
-                .map_err(|_| format!("Count '{}' is not a number", count))?;
-            let from = words.next().ok_or("No from for move instruction")?;
-            let from = from
-                .parse::<usize>()
-                .map_err(|_| format!("From '{}' is not a number", from))?;
-            let to = words.next().ok_or("No to for move instruction")?;
-            let to = to
-                .parse::<usize>()
-                .map_err(|_| format!("To '{}' is not a number", to))?;
-            Ok(Instruction::Move { count, from, to })
+            let count = argument_from_str(line, words.next(), "count")?;
+            keyword_from_str(line, words.next(), "from", "from")?;
+            let from = argument_from_str(line, words.next(), "from")?;
+            keyword_from_str(line, words.next(), "to", "to")?;
+            let to = argument_from_str(line, words.next(), "to")?;
+            Instruction::Move { count, from, to }
+        }
+        "swap" => {
+            let first = argument_from_str(line, words.next(), "first stack")?;
+            keyword_from_str(line, words.next(), "and", "second stack")?;
+            let second = argument_from_str(line, words.next(), "second stack")?;
+            Instruction::Swap { first, second }
+        }
+        "reverse" => {
+            let stack = argument_from_str(line, words.next(), "stack")?;
+            Instruction::Reverse { stack }
+        }
+        "rotate" => {
+            let count = argument_from_str(line, words.next(), "count")?;
+            keyword_from_str(line, words.next(), "on", "stack")?;
+            let stack = argument_from_str(line, words.next(), "stack")?;
+            Instruction::Rotate { count, stack }
+        }
+        "insert" => {
+            let label = label_from_str(line, words.next())?;
+            keyword_from_str(line, words.next(), "on", "stack")?;
+            let stack = argument_from_str(line, words.next(), "stack")?;
+            Instruction::Insert { label, stack }
+        }
+        "remove" => {
+            keyword_from_str(line, words.next(), "from", "stack")?;
+            let stack = argument_from_str(line, words.next(), "stack")?;
+            Instruction::Remove { stack }
+        }
+        _ => {
+            let kind = ParseErrorKind::UnknownInstruction(instruction.to_string());
+            return Err(ParseError::new(kind, Span::of(1, line, instruction)));
+        }
+    };
+
+    if let Some(word) = words.next() {
+        let kind = ParseErrorKind::TrailingWord(word.to_string());
+        return Err(ParseError::new(kind, Span::of(1, line, word)));
+    }
+    Ok(instruction)
+}
+
+/// Checks that `word` is the `keyword` introducing `argument`, which is missing if the keyword is.
+fn keyword_from_str(
+    line: &str,
+    word: Option<&str>,
+    keyword: &'static str,
+    argument: &'static str,
+) -> Result<(), ParseError> {
+    match word {
+        Some(word) if word == keyword => Ok(()),
+        Some(word) => {
+            let kind = ParseErrorKind::UnexpectedWord(keyword, word.to_string());
+            Err(ParseError::new(kind, Span::of(1, line, word)))
+        }
+        None => {
+            let kind = ParseErrorKind::MissingArgument(argument);
+            let span = Span::of(1, line, &line[line.len()..]);
+            Err(ParseError::new(kind, span))
         }
-        _ => Err(format!("Unknown instruction '{}'", instruction)),
     }
 }
 
+fn argument_from_str(
+    line: &str,
+    word: Option<&str>,
+    argument: &'static str,
+) -> Result<usize, ParseError> {
+    let word = word.ok_or_else(|| {
+        let kind = ParseErrorKind::MissingArgument(argument);
+        ParseError::new(kind, Span::of(1, line, &line[line.len()..]))
+    })?;
+
+    word.parse::<usize>().map_err(|_| {
+        let kind = ParseErrorKind::InvalidArgument(argument, word.to_string());
+        ParseError::new(kind, Span::of(1, line, word))
+    })
+}
+
+fn label_from_str(line: &str, word: Option<&str>) -> Result<String, ParseError> {
+    let word = word.filter(|word| !word.is_empty()).ok_or_else(|| {
+        let kind = ParseErrorKind::MissingArgument("label");
+        ParseError::new(kind, Span::of(1, line, &line[line.len()..]))
+    })?;
+
+    if word.contains(['[', ']']) {
+        let kind = ParseErrorKind::InvalidLabel(word.to_string());
+        return Err(ParseError::new(kind, Span::of(1, line, word)));
+    }
+    Ok(word.to_string())
+}
+
+#[derive(Clone)]
 pub struct CargoCrane {
     ship: Ship,
     instructions: Vec<Instruction>,
+    spans: Vec<Span>,
 }
 
 impl TryFrom<&str> for CargoCrane {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let ship = Ship::try_from(value)?;
-        let instructions = instructions_from_str(value)?;
+        let (instructions, spans) = sourced_instructions_from_str(value)?.into_iter().unzip();
 
-        Ok(CargoCrane { ship, instructions })
+        Ok(CargoCrane {
+            ship,
+            instructions,
+            spans,
+        })
     }
 }
 
@@ -142,44 +685,79 @@ impl CargoCrane {
         &self.ship
     }
 
-    pub fn execute_instructions_crate_mover_9000(&mut self) -> Result<(), String> {
-        for instruction in self.instructions.iter() {
-            match instruction {
-                Instruction::Move { count, from, to } => {
-                    for _ in 0..*count {
-                        let crate_char = {
-                            let from_stack =
-                                self.ship.stacks.get_mut(*from - 1).ok_or("No from stack")?;
-                            from_stack.pop_back().ok_or("No crate to move")?
-                        };
+    /// Checks that every instruction succeeds with the crane `model`, by executing them on a copy
+    /// of the ship, so the ship itself is not changed.
+    pub fn validate(&self, model: &dyn CraneModel) -> Result<(), InstructionError> {
+        Self::execute_on(&mut self.ship.clone(), &self.instructions, model)
+            .map_err(|(index, error)| self.instruction_error(index, error))
+    }
 
-                        let to_stack = self.ship.stacks.get_mut(*to - 1).ok_or("No to stack")?;
-                        to_stack.push_back(crate_char);
-                    }
-                }
-            }
+    /// Executes the instructions on the ship, failing with the index of the first one that fails.
+    fn execute_on(
+        ship: &mut Ship,
+        instructions: &[Instruction],
+        model: &dyn CraneModel,
+    ) -> Result<(), (usize, CraneError)> {
+        for (index, instruction) in instructions.iter().enumerate() {
+            ship.execute(instruction, model)
+                .map_err(|error| (index, error))?;
         }
         Ok(())
     }
 
-    pub fn execute_instructions_crate_mover_9001(&mut self) -> Result<(), String> {
-        for instruction in self.instructions.iter() {
-            match instruction {
-                Instruction::Move { count, from, to } => {
-                    let crate_chars = {
-                        let from_stack =
-                            self.ship.stacks.get_mut(*from - 1).ok_or("No from stack")?;
-                        from_stack
-                            .drain(from_stack.len() - *count..)
-                            .collect::<VecDeque<_>>()
-                    };
-
-                    let to_stack = self.ship.stacks.get_mut(*to - 1).ok_or("No to stack")?;
-                    to_stack.extend(crate_chars);
-                }
-            }
+    pub(crate) fn instruction_error(&self, index: usize, error: CraneError) -> InstructionError {
+        InstructionError {
+            index,
+            span: self.spans[index],
+            error,
         }
-        Ok(())
+    }
+
+    /// Executes every instruction, moving the crates as the crane `model` does. The instructions
+    /// are validated first, so the ship is not changed if any of them fails.
+    pub fn execute_instructions(&mut self, model: &dyn CraneModel) -> Result<(), InstructionError> {
+        self.validate(model)?;
+
+        Self::execute_on(&mut self.ship, &self.instructions, model)
+            .map_err(|(index, error)| self.instruction_error(index, error))
+    }
+
+    /// Execution of the instructions one at a time, starting from the current ship.
+    pub fn execution<'a>(&'a self, model: &'a dyn CraneModel) -> Execution<'a> {
+        Execution::new(self, model)
+    }
+
+    pub fn execute_instructions_crate_mover_9000(&mut self) -> Result<(), InstructionError> {
+        self.execute_instructions(&CrateMover9000)
+    }
+
+    pub fn execute_instructions_crate_mover_9001(&mut self) -> Result<(), InstructionError> {
+        self.execute_instructions(&CrateMover9001)
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = CargoCrane;
+    type Error = Error;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(CargoCrane::try_from(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        let mut crane = input.clone();
+        crane.execute_instructions_crate_mover_9000()?;
+        Ok(crane.ship().crates_message_to_elves())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        let mut crane = input.clone();
+        crane.execute_instructions_crate_mover_9001()?;
+        Ok(crane.ship().crates_message_to_elves())
     }
 }
 
@@ -192,9 +770,125 @@ mod tests {
         let ship = Ship::try_from(input()).unwrap();
 
         assert_eq!(ship.stacks().len(), 3);
-        assert_eq!(ship.stacks()[0], VecDeque::from(['Z', 'N']));
-        assert_eq!(ship.stacks()[1], VecDeque::from(['M', 'C', 'D']));
-        assert_eq!(ship.stacks()[2], VecDeque::from(['P']));
+        assert_eq!(ship.stacks()[0], ["Z", "N"]);
+        assert_eq!(ship.stacks()[1], ["M", "C", "D"]);
+        assert_eq!(ship.stacks()[2], ["P"]);
+    }
+
+    #[test]
+    fn it_loads_crates_with_long_labels_and_more_than_nine_stacks() {
+        let input = [
+            "                                        [K1]",
+            "[AB]                             [J]    [K2]",
+            "[C]  [D]                        [Jé]    [K3] [L]",
+            " 1   2   3   4   5   6   7   8   9   10   11  12",
+        ]
+        .join("\n");
+
+        let ship = Ship::try_from(input.as_str()).unwrap();
+
+        assert_eq!(ship.stacks().len(), 12);
+        assert_eq!(ship.stacks()[0], ["C", "AB"]);
+        assert_eq!(ship.stacks()[1], ["D"]);
+        assert_eq!(ship.stacks()[8], ["Jé", "J"]);
+        assert_eq!(ship.stacks()[10], ["K3", "K2", "K1"]);
+        assert_eq!(ship.stacks()[11], ["L"]);
+        assert_eq!(ship.crates_message_to_elves(), "ABDJK1L");
+    }
+
+    #[test]
+    fn it_renders_the_stacks_like_the_input() {
+        let mut cargo_crane = CargoCrane::try_from(input()).unwrap();
+        let drawing = input().lines().take(4).collect::<Vec<_>>().join("\n");
+
+        assert_eq!(cargo_crane.ship().to_string(), drawing);
+
+        cargo_crane.execute_instructions_crate_mover_9000().unwrap();
+        assert_eq!(
+            cargo_crane.ship().to_string(),
+            "        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3 "
+        );
+    }
+
+    #[test]
+    fn it_parses_the_rendered_stacks_back() {
+        let input = [
+            "                                        [K1]",
+            "[AB]                             [J]    [K2]",
+            "[C]  [D]                        [Jé]    [K3] [L]",
+            " 1   2   3   4   5   6   7   8   9   10   11  12",
+        ]
+        .join("\n");
+        let ship = Ship::try_from(input.as_str()).unwrap();
+
+        let rendered = ship.to_string();
+
+        assert_eq!(
+            rendered.lines().collect::<Vec<_>>(),
+            vec![
+                "                                          [K1]",
+                "[AB]                             [J]      [K2]",
+                "[C]  [D]                         [Jé]     [K3] [L]",
+                " 1    2   3   4   5   6   7   8   9   10   11  12 ",
+            ]
+        );
+        assert_eq!(
+            Ship::try_from(rendered.as_str()).unwrap().stacks(),
+            ship.stacks()
+        );
+    }
+
+    #[test]
+    fn it_reports_where_the_drawing_is_misaligned() {
+        let errors = [
+            "[A]\n  [B]\n 1   2",
+            "[A] [B]\n 1",
+            "[A] B\n 1   2",
+            "[A] [B\n 1   2",
+            "[A] []\n 1   2",
+            "[A] [B]\n 1   3",
+        ]
+        .map(|input| Ship::try_from(input).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::MisalignedCrate("B".to_string()),
+                Location::new(2, 4)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(
+                ParseErrorKind::MisalignedCrate("B".to_string()),
+                Location::new(1, 6)
+            )
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::NotACrate("B".to_string()),
+                Location::new(1, 5)
+            )
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(ParseErrorKind::UnclosedCrate, Location::new(1, 5))
+        );
+        assert_eq!(
+            errors[4],
+            ParseError::new(ParseErrorKind::EmptyCrate, Span::new(1, 5, 2))
+        );
+        assert_eq!(
+            errors[5],
+            ParseError::new(
+                ParseErrorKind::UnexpectedStackId {
+                    expected: 2,
+                    found: 3
+                },
+                Location::new(2, 6)
+            )
+        );
     }
 
     #[test]
@@ -259,9 +953,9 @@ mod tests {
         let ship = cargo_crane.ship();
 
         assert_eq!(ship.stacks().len(), 3);
-        assert_eq!(ship.stacks()[0], VecDeque::from(['C']));
-        assert_eq!(ship.stacks()[1], VecDeque::from(['M']));
-        assert_eq!(ship.stacks()[2], VecDeque::from(['P', 'D', 'N', 'Z']));
+        assert_eq!(ship.stacks()[0], ["C"]);
+        assert_eq!(ship.stacks()[1], ["M"]);
+        assert_eq!(ship.stacks()[2], ["P", "D", "N", "Z"]);
     }
 
     #[test]
@@ -286,6 +980,301 @@ mod tests {
         assert_eq!(ship_after.crates_message_to_elves(), "MCD");
     }
 
+    #[test]
+    fn it_reports_where_an_instruction_is_malformed() {
+        let errors = ["move 1 from x to 1", "move 1 from 2", "lift 1 from 2 to 1"]
+            .map(|line| instruction_from_str(line).unwrap_err());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::InvalidArgument("from", "x".to_string()),
+                Location::new(1, 13)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(ParseErrorKind::MissingArgument("to"), Location::new(1, 14))
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::UnknownInstruction("lift".to_string()),
+                Span::new(1, 1, 4)
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_the_line_of_the_malformed_instruction_in_the_input() {
+        let input = input().replace("move 2 from 2 to 1", "move 2 from 2 to one");
+
+        let error = CargoCrane::try_from(input.as_str()).err().unwrap();
+
+        assert_eq!(error.location(), Location::new(8, 18));
+    }
+
+    #[test]
+    fn it_fails_to_move_crates_from_an_empty_stack() {
+        let input = input().replace("move 1 from 1 to 2", "move 4 from 1 to 2");
+        let mut cargo_crane = CargoCrane::try_from(input.as_str()).unwrap();
+
+        let result = cargo_crane.execute_instructions_crate_mover_9000();
+
+        assert_eq!(
+            result,
+            Err(InstructionError {
+                index: 3,
+                span: Span::new(9, 1, 18),
+                error: CraneError::EmptyStack(1)
+            })
+        );
+        assert_eq!(cargo_crane.ship().crates_message_to_elves(), "NDP");
+    }
+
+    #[test]
+    fn it_validates_the_instructions_before_executing_them() {
+        let inputs = [
+            input().replace("move 1 from 1 to 2", "move 1 from 0 to 2"),
+            input().replace("move 2 from 2 to 1", "move 5 from 2 to 1"),
+            format!("{}\nremove from 2\nswap 2 and 3\nreverse 4", input()),
+        ];
+
+        let errors = inputs.map(|input| {
+            let mut cargo_crane = CargoCrane::try_from(input.as_str()).unwrap();
+            cargo_crane
+                .execute_instructions(&CrateMover9001)
+                .unwrap_err()
+        });
+
+        assert_eq!(errors[0].error, CraneError::NoSuchStack(0));
+        assert_eq!((errors[0].index, errors[0].line()), (3, 9));
+        assert_eq!(
+            errors[1].error,
+            CraneError::NotEnoughCrates {
+                stack: 2,
+                requested: 5,
+                available: 2
+            }
+        );
+        assert_eq!((errors[1].index, errors[1].line()), (2, 8));
+        assert_eq!(errors[2].error, CraneError::NoSuchStack(4));
+        assert_eq!((errors[2].index, errors[2].line()), (6, 12));
+        assert_eq!(
+            errors[2].to_string(),
+            "instruction 7 at line 12 failed: there is no stack 4"
+        );
+    }
+
+    #[test]
+    fn it_validates_the_instructions_with_the_crane_model() {
+        /// Crane dropping the last crate of every move into the sea.
+        struct Leaky;
+
+        impl CraneModel for Leaky {
+            fn name(&self) -> &str {
+                "leaky"
+            }
+
+            fn move_crates(
+                &self,
+                ship: &mut Ship,
+                count: usize,
+                from: usize,
+                to: usize,
+            ) -> Result<(), CraneError> {
+                let mut crates = ship.take(count, from, to)?;
+                crates.pop();
+                ship.put(crates, to)
+            }
+        }
+        let mut cargo_crane = CargoCrane::try_from(input()).unwrap();
+
+        let error = cargo_crane.execute_instructions(&Leaky).unwrap_err();
+
+        assert_eq!(cargo_crane.validate(&CrateMover9000), Ok(()));
+        assert_eq!(cargo_crane.validate(&Leaky), Err(error.clone()));
+        assert_eq!(
+            (error.index, error.error),
+            (
+                1,
+                CraneError::NotEnoughCrates {
+                    stack: 1,
+                    requested: 3,
+                    available: 2
+                }
+            )
+        );
+        assert_eq!(cargo_crane.ship().crates_message_to_elves(), "NDP");
+    }
+
+    #[test]
+    fn it_checks_the_keywords_of_an_instruction() {
+        let errors = [
+            "move 1 xx 2 yy 3",
+            "swap 1 with 3",
+            "move 1 from 2 to 3 now",
+        ]
+        .map(|line| instruction_from_str(line).unwrap_err());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::UnexpectedWord("from", "xx".to_string()),
+                Span::new(1, 8, 2)
+            )
+        );
+        assert_eq!(
+            errors[1].kind(),
+            &ParseErrorKind::UnexpectedWord("and", "with".to_string())
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::TrailingWord("now".to_string()),
+                Span::new(1, 20, 3)
+            )
+        );
+    }
+
+    #[test]
+    fn it_loads_the_rearranging_instructions() {
+        let instructions = [
+            "swap 1 and 3",
+            "reverse 2",
+            "rotate 3 on 1",
+            "insert AB on 2",
+            "remove from 3",
+        ]
+        .map(|line| instruction_from_str(line).unwrap());
+
+        assert_eq!(
+            instructions,
+            [
+                Instruction::Swap {
+                    first: 1,
+                    second: 3
+                },
+                Instruction::Reverse { stack: 2 },
+                Instruction::Rotate { count: 3, stack: 1 },
+                Instruction::Insert {
+                    label: "AB".to_string(),
+                    stack: 2
+                },
+                Instruction::Remove { stack: 3 },
+            ]
+        );
+        assert_eq!(
+            CargoCrane::try_from([input(), "insert [A] on 2"].join("\n").as_str()).err(),
+            Some(ParseError::new(
+                ParseErrorKind::InvalidLabel("[A]".to_string()),
+                Span::new(10, 8, 3)
+            ))
+        );
+        assert_eq!(
+            instruction_from_str("remove from").unwrap_err(),
+            ParseError::new(
+                ParseErrorKind::MissingArgument("stack"),
+                Location::new(1, 12)
+            )
+        );
+    }
+
+    #[test]
+    fn it_rearranges_the_stacks_with_both_crane_models() {
+        let input = [
+            input(),
+            "swap 1 and 3",
+            "reverse 3",
+            "insert XY on 1",
+            "rotate 3 on 1",
+            "remove from 2",
+        ]
+        .join("\n");
+        let mut crate_mover_9000 = CargoCrane::try_from(input.as_str()).unwrap();
+        let mut crate_mover_9001 = crate_mover_9000.clone();
+
+        crate_mover_9000
+            .execute_instructions_crate_mover_9000()
+            .unwrap();
+        crate_mover_9001
+            .execute_instructions_crate_mover_9001()
+            .unwrap();
+
+        assert_eq!(
+            crate_mover_9000.ship().stacks()[0],
+            ["P", "D", "XY", "N", "Z"]
+        );
+        assert_eq!(crate_mover_9000.ship().stacks()[1], Vec::<&str>::new());
+        assert_eq!(crate_mover_9000.ship().stacks()[2], ["C"]);
+        assert_eq!(crate_mover_9001.ship().crates_message_to_elves(), "DM");
+    }
+
+    #[test]
+    fn it_rotates_the_top_crates_of_a_stack() {
+        let mut ship = Ship::try_from("[A]\n[B]\n[C]\n[D]\n 1").unwrap();
+
+        ship.rotate(3, 1).unwrap();
+
+        assert_eq!(ship.stacks()[0], ["D", "A", "C", "B"]);
+        assert_eq!(
+            ship.rotate(5, 1),
+            Err(CraneError::NotEnoughCrates {
+                stack: 1,
+                requested: 5,
+                available: 4
+            })
+        );
+        assert_eq!(ship.swap(1, 0), Err(CraneError::NoSuchStack(0)));
+        assert_eq!(ship.remove(2), Err(CraneError::NoSuchStack(2)));
+    }
+
+    #[test]
+    fn it_executes_the_instructions_with_the_chosen_crane_model() {
+        let models: Vec<Box<dyn CraneModel>> = vec![
+            Box::new(CrateMover9000),
+            Box::new(CrateMover9001),
+            Box::new(LiftCapacity::new(2)),
+            Box::new(PairFlipper),
+        ];
+
+        let messages = models
+            .iter()
+            .map(|model| {
+                let mut cargo_crane = CargoCrane::try_from(input()).unwrap();
+                cargo_crane.execute_instructions(model.as_ref()).unwrap();
+                cargo_crane.ship().crates_message_to_elves()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(messages, vec!["CMZ", "MCD", "MCZ", "CMD"]);
+    }
+
+    #[test]
+    fn it_rolls_back_every_instruction_when_one_fails() {
+        let input = input().replace("move 1 from 1 to 2", "move 4 from 1 to 2");
+        let mut cargo_crane = CargoCrane::try_from(input.as_str()).unwrap();
+
+        let failed = cargo_crane.execute_instructions(&CrateMover9000);
+        let ship_after_failure = cargo_crane.ship().clone();
+        let failed_again = cargo_crane.execute_instructions(&CrateMover9001);
+
+        assert_eq!(failed.map_err(|e| e.error), Err(CraneError::EmptyStack(1)));
+        assert_eq!(
+            ship_after_failure,
+            CargoCrane::try_from(input.as_str()).unwrap().ship
+        );
+        assert_eq!(
+            failed_again.map_err(|e| e.error),
+            Err(CraneError::NotEnoughCrates {
+                stack: 1,
+                requested: 4,
+                available: 2
+            })
+        );
+        assert_eq!(cargo_crane.ship().crates_message_to_elves(), "NDP");
+    }
+
     fn input() -> &'static str {
         "    [D]
 [N] [C]
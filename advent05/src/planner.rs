@@ -0,0 +1,344 @@
+use crate::{CraneModel, Ship};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Ship states the planner explores before giving up, unless it is given another budget.
+pub const DEFAULT_BUDGET: usize = 100_000;
+
+/// Move instruction found by the [`Planner`], written like the instructions of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+/// What the ship has to look like after the planned moves.
+pub enum Goal {
+    /// Every stack has exactly the crates of the stack of this ship.
+    Layout(Ship),
+    /// [`Ship::crates_message_to_elves`] gives this message.
+    Message(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// The layout does not have the same stacks and crates as the ship, so no move reaches it.
+    DifferentCrates,
+    /// Every reachable ship was explored, and none of them reaches the goal.
+    Unreachable,
+    BudgetExceeded {
+        explored: usize,
+    },
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanError::DifferentCrates => {
+                write!(f, "the layout does not have the crates of the ship")
+            }
+            PlanError::Unreachable => write!(f, "no moves reach the goal"),
+            PlanError::BudgetExceeded { explored } => {
+                write!(f, "no plan found after exploring {} ships", explored)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+/// Ship found by the search, shared with the index finding it again.
+struct Found {
+    ship: Rc<Ship>,
+    moves: usize,
+    step: Option<(usize, Move)>,
+}
+
+/// Finds the fewest moves reaching a goal with a crane model, with an A* search over the ships
+/// the moves lead to.
+pub struct Planner<'a> {
+    model: &'a dyn CraneModel,
+    budget: usize,
+}
+
+impl<'a> Planner<'a> {
+    /// Planner giving up after exploring `budget` ships.
+    pub fn new(model: &'a dyn CraneModel, budget: usize) -> Planner<'a> {
+        Planner { model, budget }
+    }
+
+    pub fn plan(&self, ship: &Ship, goal: &Goal) -> Result<Vec<Move>, PlanError> {
+        if let Goal::Layout(layout) = goal {
+            if !same_crates(ship, layout) {
+                return Err(PlanError::DifferentCrates);
+            }
+        }
+
+        // Every ship found so far, with the fewest moves found to it and the move it was found
+        // from, indexed by the ship to find it again when another move leads to it.
+        let letters = letters_per_stack(ship, goal);
+        let mut found = vec![Found {
+            ship: Rc::new(ship.clone()),
+            moves: 0,
+            step: None,
+        }];
+        let mut index = HashMap::from([(Rc::clone(&found[0].ship), 0)]);
+        let mut queue =
+            BinaryHeap::from([Reverse((estimate(ship, goal, letters.as_deref()), 0, 0))]);
+        let mut explored = 0;
+
+        while let Some(Reverse((_, moves, node))) = queue.pop() {
+            if found[node].moves < moves {
+                continue;
+            }
+            let ship = Rc::clone(&found[node].ship);
+            if reaches(&ship, goal) {
+                return Ok(path(&found, node));
+            }
+
+            if explored == self.budget {
+                return Err(PlanError::BudgetExceeded { explored });
+            }
+            explored += 1;
+
+            for (next, step) in self.moves(&ship) {
+                let next = match index.get(&next) {
+                    Some(&known) if found[known].moves <= moves + 1 => continue,
+                    Some(&known) => {
+                        found[known].moves = moves + 1;
+                        found[known].step = Some((node, step));
+                        known
+                    }
+                    None => {
+                        let next = Rc::new(next);
+                        index.insert(Rc::clone(&next), found.len());
+                        found.push(Found {
+                            ship: next,
+                            moves: moves + 1,
+                            step: Some((node, step)),
+                        });
+                        found.len() - 1
+                    }
+                };
+
+                let estimate = moves + 1 + estimate(&found[next].ship, goal, letters.as_deref());
+                queue.push(Reverse((estimate, moves + 1, next)));
+            }
+        }
+
+        Err(PlanError::Unreachable)
+    }
+
+    /// Ships reached by moving any number of crates between any two stacks.
+    fn moves(&self, ship: &Ship) -> Vec<(Ship, Move)> {
+        let stacks = ship.stacks().len();
+        let mut moves = Vec::new();
+
+        for from in 1..=stacks {
+            for to in (1..=stacks).filter(|&to| to != from) {
+                for count in 1..=ship.stacks()[from - 1].len() {
+                    let mut next = ship.clone();
+                    if self.model.move_crates(&mut next, count, from, to).is_ok() {
+                        moves.push((next, Move { count, from, to }));
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+fn same_crates(ship: &Ship, layout: &Ship) -> bool {
+    let crates = |ship: &Ship| {
+        let mut crates = ship.stacks().iter().flatten().cloned().collect::<Vec<_>>();
+        crates.sort_unstable();
+        crates
+    };
+
+    ship.stacks().len() == layout.stacks().len() && crates(ship) == crates(layout)
+}
+
+fn reaches(ship: &Ship, goal: &Goal) -> bool {
+    match goal {
+        Goal::Layout(layout) => ship == layout,
+        Goal::Message(message) => ship.crates_message_to_elves() == *message,
+    }
+}
+
+/// Letter each stack needs on top to give the message, when every crate label is a single
+/// letter and the message has one letter per stack. Otherwise the stacks the letters come from
+/// cannot be told apart.
+fn letters_per_stack(ship: &Ship, goal: &Goal) -> Option<Vec<char>> {
+    let Goal::Message(message) = goal else {
+        return None;
+    };
+    let single_letters = ship
+        .stacks()
+        .iter()
+        .flatten()
+        .all(|label| label.chars().count() == 1);
+
+    (single_letters && message.chars().count() == ship.stacks().len())
+        .then(|| message.chars().collect())
+}
+
+/// Fewest moves that can reach the goal, which never overestimates because every move takes
+/// crates from a single stack and puts them on a single stack.
+fn estimate(ship: &Ship, goal: &Goal, letters: Option<&[char]>) -> usize {
+    match (goal, letters) {
+        (Goal::Layout(layout), _) => {
+            // Stacks with crates that are not in the layout need to give crates, and stacks
+            // missing crates of the layout need to receive them.
+            let (mut giving, mut receiving) = (0, 0);
+            for (stack, target) in ship.stacks().iter().zip(layout.stacks()) {
+                let prefix =
+                    stack.len() <= target.len() && stack.iter().eq(target.iter().take(stack.len()));
+                if !prefix {
+                    giving += 1;
+                } else if stack.len() < target.len() {
+                    receiving += 1;
+                }
+            }
+            giving.max(receiving)
+        }
+        (Goal::Message(_), Some(letters)) => {
+            // Every stack with another crate on top needs a move, and a move changes the top of
+            // two stacks.
+            let wrong = ship
+                .stacks()
+                .iter()
+                .zip(letters)
+                .filter(|(stack, letter)| stack.back().is_none_or(|top| !top.starts_with(**letter)))
+                .count();
+            wrong.div_ceil(2)
+        }
+        (Goal::Message(_), None) => 0,
+    }
+}
+
+fn path(found: &[Found], mut node: usize) -> Vec<Move> {
+    let mut moves = Vec::new();
+    while let Some((parent, step)) = found[node].step {
+        moves.push(step);
+        node = parent;
+    }
+    moves.reverse();
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CargoCrane, CrateMover9000, CrateMover9001};
+
+    fn drawing() -> &'static str {
+        "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3 "
+    }
+
+    /// Ship after executing the moves on the drawing.
+    fn execute(moves: &[Move], model: &dyn CraneModel) -> Ship {
+        let instructions = moves.iter().map(Move::to_string).collect::<Vec<_>>();
+        let input = format!("{}\n\n{}", drawing(), instructions.join("\n"));
+        let mut cargo_crane = CargoCrane::try_from(input.as_str()).unwrap();
+        cargo_crane.execute_instructions(model).unwrap();
+        cargo_crane.ship().clone()
+    }
+
+    #[test]
+    fn it_plans_the_fewest_moves_to_reach_a_layout() {
+        let ship = Ship::try_from(drawing()).unwrap();
+        let layout =
+            Ship::try_from("        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3 ")
+                .unwrap();
+
+        let plan = Planner::new(&CrateMover9000, DEFAULT_BUDGET)
+            .plan(&ship, &Goal::Layout(layout.clone()))
+            .unwrap();
+
+        assert_eq!(plan.len(), 3);
+        assert_eq!(execute(&plan, &CrateMover9000), layout);
+    }
+
+    #[test]
+    fn it_plans_the_moves_for_the_chosen_crane_model() {
+        let ship = Ship::try_from(drawing()).unwrap();
+        let goal = Goal::Message("MCD".to_string());
+
+        let plan = Planner::new(&CrateMover9001, DEFAULT_BUDGET)
+            .plan(&ship, &goal)
+            .unwrap();
+
+        assert_eq!(plan.len(), 3);
+        assert_eq!(
+            execute(&plan, &CrateMover9001).crates_message_to_elves(),
+            "MCD"
+        );
+        assert_eq!(
+            Planner::new(&CrateMover9001, DEFAULT_BUDGET)
+                .plan(&ship, &Goal::Message("NDP".to_string())),
+            Ok(vec![])
+        );
+        assert_eq!(
+            Move {
+                count: 2,
+                from: 1,
+                to: 3
+            }
+            .to_string(),
+            "move 2 from 1 to 3"
+        );
+    }
+
+    #[test]
+    fn it_reports_when_there_is_no_plan() {
+        let ship = Ship::try_from("[A] [B]\n 1   2").unwrap();
+        let other_crates = Ship::try_from("[A] [C]\n 1   2").unwrap();
+        let planner = Planner::new(&CrateMover9000, DEFAULT_BUDGET);
+
+        assert_eq!(
+            planner.plan(&ship, &Goal::Layout(other_crates)),
+            Err(PlanError::DifferentCrates)
+        );
+        assert_eq!(
+            planner.plan(&ship, &Goal::Message("C".to_string())),
+            Err(PlanError::Unreachable)
+        );
+        assert_eq!(
+            Planner::new(&CrateMover9000, 1).plan(&ship, &Goal::Message("BA".to_string())),
+            Err(PlanError::BudgetExceeded { explored: 1 })
+        );
+    }
+
+    #[test]
+    fn it_plans_the_fewest_moves_for_a_message_of_long_labels() {
+        let ship = Ship::try_from("[AB] [C] [X]\n 1   2   3").unwrap();
+        let goal = Goal::Message("ABC".to_string());
+
+        let plan = Planner::new(&CrateMover9000, DEFAULT_BUDGET)
+            .plan(&ship, &goal)
+            .unwrap();
+
+        assert_eq!(letters_per_stack(&ship, &goal), None);
+        assert_eq!(plan.len(), 1);
+        let mut planned = ship.clone();
+        for step in plan {
+            CrateMover9000
+                .move_crates(&mut planned, step.count, step.from, step.to)
+                .unwrap();
+        }
+        assert_eq!(planned.crates_message_to_elves(), "ABC");
+    }
+}
@@ -1,15 +1,52 @@
 #![allow(unused)]
 
+use advent::{Diagnostic, Solution, Span};
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
 
-pub fn marker_position(mut input: impl Iterator<Item = char>) -> Option<usize> {
-    let start_message = 14;
-    let mut found_chars = VecDeque::with_capacity(start_message);
+const START_OF_PACKET_MARKER_SIZE: usize = 4;
+const START_OF_MESSAGE_MARKER_SIZE: usize = 14;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    MarkerNotFound { size: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MarkerNotFound { size } => write!(
+                f,
+                "no marker of {} different characters in the datastream",
+                size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        None
+    }
+}
+
+pub fn marker_position(input: impl Iterator<Item = char>) -> Option<usize> {
+    distinct_window_position(input, START_OF_PACKET_MARKER_SIZE)
+}
+
+pub fn start_of_message_position(input: impl Iterator<Item = char>) -> Option<usize> {
+    distinct_window_position(input, START_OF_MESSAGE_MARKER_SIZE)
+}
+
+fn distinct_window_position(input: impl Iterator<Item = char>, size: usize) -> Option<usize> {
+    let mut found_chars = VecDeque::with_capacity(size);
 
     for (step, c) in input.enumerate() {
-        if found_chars.len() < start_message {
+        if found_chars.len() < size {
             found_chars.push_back(c);
-            if found_chars.len() == start_message && all_chars_are_different(&found_chars) {
+            if found_chars.len() == size && all_chars_are_different(&found_chars) {
                 return Some(step + 1);
             }
             continue;
@@ -36,6 +73,31 @@ fn all_chars_are_different(chars: &VecDeque<char>) -> bool {
     true
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = String;
+    type Error = Error;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        marker_position(input.chars()).ok_or(Error::MarkerNotFound {
+            size: START_OF_PACKET_MARKER_SIZE,
+        })
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        start_of_message_position(input.chars()).ok_or(Error::MarkerNotFound {
+            size: START_OF_MESSAGE_MARKER_SIZE,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +157,24 @@ mod tests {
 
         assert_eq!(markers, results);
     }
+
+    #[test]
+    fn it_reports_start_of_message_found_with_data_tests() {
+        let data_tests = [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(),
+            "bvwbjplbgvbhsrlpgdmjqwftvncz".chars(),
+            "nppdvjthqldpwncqszvftbrmjlhg".chars(),
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".chars(),
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".chars(),
+        ];
+
+        let results = [19_usize, 23, 23, 29, 26];
+
+        let markers = data_tests
+            .into_iter()
+            .filter_map(start_of_message_position)
+            .collect::<Vec<_>>();
+
+        assert_eq!(markers, results);
+    }
 }
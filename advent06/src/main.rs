@@ -1,10 +1,12 @@
-use advent06::marker_position;
+use advent06::{marker_position, start_of_message_position};
 
 fn main() {
     let count = marker_position(input().chars());
+    let message_count = start_of_message_position(input().chars());
     println!("count: {}", count.unwrap());
+    println!("start of message count: {}", message_count.unwrap());
 }
 
 fn input() -> &'static str {
     include_str!("input.txt")
-}
\ No newline at end of file
+}
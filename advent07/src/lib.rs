@@ -1,9 +1,80 @@
 #![allow(unused)]
 
+use advent::{Diagnostic, Location, Solution, Span};
 use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
 use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+const SMALL_DIR_MAX_SIZE: usize = 100000;
+const DISK_SIZE: usize = 70000000;
+const REQUIRED_UNUSED_SPACE: usize = 30000000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnknownCommand(String),
+    MissingPath,
+    NoSuchDirectory(String),
+    NotADirectory(String),
+    MissingName,
+    InvalidSize(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            ParseErrorKind::MissingPath => write!(f, "cd requires a path"),
+            ParseErrorKind::NoSuchDirectory(path) => write!(f, "no such directory: {}", path),
+            ParseErrorKind::NotADirectory(name) => write!(f, "{} is not a directory", name),
+            ParseErrorKind::MissingName => write!(f, "directories and files require a name"),
+            ParseErrorKind::InvalidSize(size) => write!(f, "invalid file size '{}'", size),
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    NotEnoughSpace,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::NotEnoughSpace => write!(f, "no directory frees enough space"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::NotEnoughSpace => None,
+        }
+    }
+}
 
-enum Descriptor {
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::Parse(e) => Some(e.span()),
+            Error::NotEnoughSpace => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+pub enum Descriptor {
     Root {
         children: Vec<Rc<RefCell<Descriptor>>>,
     },
@@ -110,7 +181,8 @@ impl Descriptor {
         }
     }
 
-    pub fn add_child(&mut self, child: Descriptor) -> Result<(), String> {
+    /// Adds the child to this directory, giving it back if this descriptor is a file.
+    pub fn add_child(&mut self, child: Descriptor) -> Result<(), Descriptor> {
         match self {
             Descriptor::Root { children } => {
                 children.push(Rc::new(RefCell::new(child)));
@@ -120,7 +192,7 @@ impl Descriptor {
                 children.push(Rc::new(RefCell::new(child)));
                 Ok(())
             }
-            Descriptor::File { .. } => Err("Cannot add child to file".to_string()),
+            Descriptor::File { .. } => Err(child),
         }
     }
 
@@ -137,47 +209,87 @@ impl Descriptor {
     }
 }
 
-struct Filesystem {
+pub struct Filesystem {
     root: Rc<RefCell<Descriptor>>,
 }
 
-impl Filesystem {
-    pub fn from_str(input: &str) -> Result<Self, String> {
+impl FromStr for Filesystem {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         let root = Rc::new(RefCell::new(Descriptor::Root { children: vec![] }));
         let mut current_descriptor = root.clone();
 
-        for line in input.lines() {
-            current_descriptor =
-                Self::update_fs_from_line(line.trim(), current_descriptor, root.clone())?;
+        for (i, line) in input.lines().enumerate() {
+            current_descriptor = Self::update_fs_from_line(line, current_descriptor, root.clone())
+                .map_err(|e| e.with_line_offset(i))?;
         }
 
         Ok(Filesystem { root })
     }
+}
 
+impl Filesystem {
     pub fn root(&self) -> Rc<RefCell<Descriptor>> {
         self.root.clone()
     }
 
+    pub fn dir_sizes(&self) -> Vec<usize> {
+        self.root
+            .borrow()
+            .tree()
+            .iter()
+            .filter(|d| d.borrow().is_dir())
+            .map(|d| d.borrow().size())
+            .collect()
+    }
+
+    pub fn total_size_of_dirs_at_most(&self, max_size: usize) -> usize {
+        self.dir_sizes()
+            .into_iter()
+            .filter(|size| *size <= max_size)
+            .sum()
+    }
+
+    pub fn smallest_dir_size_to_free(
+        &self,
+        disk_size: usize,
+        required_unused: usize,
+    ) -> Option<usize> {
+        let used = self.root.borrow().size();
+        let unused = disk_size.checked_sub(used)?;
+        let to_free = required_unused.saturating_sub(unused);
+
+        self.dir_sizes()
+            .into_iter()
+            .chain(std::iter::once(used))
+            .filter(|size| *size >= to_free)
+            .min()
+    }
+
     fn update_fs_from_line(
         line: &str,
         current: Rc<RefCell<Descriptor>>,
         root: Rc<RefCell<Descriptor>>,
-    ) -> Result<Rc<RefCell<Descriptor>>, String> {
-        let is_command = line.starts_with('$');
+    ) -> Result<Rc<RefCell<Descriptor>>, ParseError> {
+        let trimmed_line = line.trim();
+        let is_command = trimmed_line.starts_with('$');
 
         if is_command {
-            Self::update_fs_from_command(line.strip_prefix("$ ").unwrap_or(line), current, root)
+            let command = trimmed_line.strip_prefix("$ ").unwrap_or(trimmed_line);
+            Self::update_fs_from_command(command, line, current, root)
         } else {
-            Self::update_fs_from_ls(line, current.clone())?;
+            Self::update_fs_from_ls(trimmed_line, line, current.clone())?;
             Ok(current)
         }
     }
 
     fn update_fs_from_command(
         command: &str,
+        line: &str,
         current: Rc<RefCell<Descriptor>>,
         root: Rc<RefCell<Descriptor>>,
-    ) -> Result<Rc<RefCell<Descriptor>>, String> {
+    ) -> Result<Rc<RefCell<Descriptor>>, ParseError> {
         let mut command_words = command.split(' ');
 
         let command = command_words.next();
@@ -187,23 +299,43 @@ impl Filesystem {
                 match path {
                     Some("/") => Ok(root),
                     Some("..") => Ok(current.borrow().parent().unwrap_or(root)),
-                    Some(path) => current
-                        .borrow()
-                        .child(path)
-                        .ok_or(format!("No such directory: {}", path)),
-                    None => Err("cd requires a path".to_string()),
+                    Some(path) => {
+                        let child = current.borrow().child(path).ok_or_else(|| {
+                            let kind = ParseErrorKind::NoSuchDirectory(path.to_string());
+                            ParseError::new(kind, Span::of(1, line, path))
+                        })?;
+                        if child.borrow().is_file() {
+                            let kind = ParseErrorKind::NotADirectory(path.to_string());
+                            return Err(ParseError::new(kind, Span::of(1, line, path)));
+                        }
+                        Ok(child)
+                    }
+                    None => Err(ParseError::new(
+                        ParseErrorKind::MissingPath,
+                        Self::end_of_line(line),
+                    )),
                 }
             }
             Some("ls") => Ok(current),
-            _ => Err("unknown command")?,
+            Some(command) => {
+                let kind = ParseErrorKind::UnknownCommand(command.to_string());
+                Err(ParseError::new(kind, Span::of(1, line, command)))
+            }
+            None => unreachable!("split always returns at least one element"),
         }
     }
 
-    fn update_fs_from_ls(line: &str, mut current: Rc<RefCell<Descriptor>>) -> Result<(), String> {
-        let mut line_words = line.split(' ');
+    fn update_fs_from_ls(
+        ls_line: &str,
+        line: &str,
+        mut current: Rc<RefCell<Descriptor>>,
+    ) -> Result<(), ParseError> {
+        let mut line_words = ls_line.split(' ');
         match line_words.next() {
             Some("dir") => {
-                let name = line_words.next().ok_or("directory requires a name")?;
+                let name = line_words.next().ok_or_else(|| {
+                    ParseError::new(ParseErrorKind::MissingName, Self::end_of_line(line))
+                })?;
                 if current.borrow().child(name).is_some() {
                     // skip, already exists
                     return Ok(());
@@ -214,12 +346,16 @@ impl Filesystem {
                     children: vec![],
                     parent: Rc::downgrade(&current),
                 };
-                current.borrow_mut().add_child(new_dir)?;
-                Ok(())
+                Self::add_child(&current, new_dir, line)
             }
-            Some(size) => {
-                let size = size.parse::<usize>().map_err(|_| "invalid size")?;
-                let name = line_words.next().ok_or("file requires a name")?;
+            Some(size_word) => {
+                let size = size_word.parse::<usize>().map_err(|_| {
+                    let kind = ParseErrorKind::InvalidSize(size_word.to_string());
+                    ParseError::new(kind, Span::of(1, line, size_word))
+                })?;
+                let name = line_words.next().ok_or_else(|| {
+                    ParseError::new(ParseErrorKind::MissingName, Self::end_of_line(line))
+                })?;
                 if current.borrow().child(name).is_some() {
                     // skip, already exists
                     return Ok(());
@@ -230,12 +366,49 @@ impl Filesystem {
                     size,
                     parent: Rc::downgrade(&current),
                 };
-                current.borrow_mut().add_child(new_file)?;
-                Ok(())
+                Self::add_child(&current, new_file, line)
             }
-            None => Err("ls line must start with dir or file size".to_string()),
+            None => unreachable!("split always returns at least one element"),
         }
     }
+
+    fn add_child(
+        current: &Rc<RefCell<Descriptor>>,
+        child: Descriptor,
+        line: &str,
+    ) -> Result<(), ParseError> {
+        current.borrow_mut().add_child(child).map_err(|_| {
+            let kind = ParseErrorKind::NotADirectory(current.borrow().name());
+            ParseError::new(kind, Span::of(1, line, line.trim_start()))
+        })
+    }
+
+    fn end_of_line(line: &str) -> Span {
+        Span::of(1, line, &line[line.len()..])
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Filesystem;
+    type Error = Error;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(Filesystem::from_str(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(input.total_size_of_dirs_at_most(SMALL_DIR_MAX_SIZE))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        input
+            .smallest_dir_size_to_free(DISK_SIZE, REQUIRED_UNUSED_SPACE)
+            .ok_or(Error::NotEnoughSpace)
+    }
 }
 
 #[cfg(test)]
@@ -304,6 +477,62 @@ mod tests {
         assert_eq!(dirs.last().unwrap().borrow().name(), "e");
     }
 
+    #[test]
+    fn it_returns_the_total_size_of_the_dirs_that_are_lower_or_equal_than_100000() {
+        let filesystem = Filesystem::from_str(input()).unwrap();
+
+        assert_eq!(filesystem.total_size_of_dirs_at_most(100000), 95437);
+    }
+
+    #[test]
+    fn it_returns_the_size_of_the_smallest_dir_that_frees_enough_space() {
+        let filesystem = Filesystem::from_str(input()).unwrap();
+
+        let size = filesystem.smallest_dir_size_to_free(70000000, 30000000);
+
+        assert_eq!(size, Some(24933642));
+    }
+
+    #[test]
+    fn it_reports_where_the_terminal_output_is_malformed() {
+        let errors = [
+            "$ cd /\n$ ls\ndir a\n$ cd b",
+            "$ cd /\n$ ls\n12a b.txt",
+            "$ cd /\n$ pwd",
+            "$ cd /\n$ ls\n14848514 b.txt\n$ cd b.txt",
+        ]
+        .map(|input| Filesystem::from_str(input).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::NoSuchDirectory("b".to_string()),
+                Location::new(4, 6)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(
+                ParseErrorKind::InvalidSize("12a".to_string()),
+                Span::new(3, 1, 3)
+            )
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::UnknownCommand("pwd".to_string()),
+                Span::new(2, 3, 3)
+            )
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(
+                ParseErrorKind::NotADirectory("b.txt".to_string()),
+                Span::new(4, 6, 5)
+            )
+        );
+    }
+
     fn input() -> &'static str {
         "\
 $ cd /
@@ -1,8 +1,32 @@
 #![allow(unused)]
 
+use advent::{Location, Solution};
 use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-struct Tree {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidHeight(char),
+    InvalidRowWidth { expected: usize, found: usize },
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::InvalidHeight(c) => write!(f, "tree height '{}' is not a digit", c),
+            ParseErrorKind::InvalidRowWidth { expected, found } => write!(
+                f,
+                "row has {} trees, but the previous ones have {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+pub struct Tree {
     height: u8,
 }
 
@@ -16,23 +40,37 @@ impl Tree {
     }
 }
 
-struct Forest {
+pub struct Forest {
     trees: Vec<Tree>,
     width: usize,
     height: usize,
 }
 
-impl Forest {
-    pub fn from_str(input: &str) -> Result<Forest, String> {
+impl FromStr for Forest {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut trees = Vec::new();
         let mut width = 0;
         let mut height = 0;
 
-        for line in input.lines() {
-            width = line.trim().len();
-            for char in line.chars() {
-                let tree = Tree::new(char.to_digit(10).unwrap_or(0) as u8);
-                trees.push(tree);
+        for (y, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if y > 0 && line.chars().count() != width {
+                let kind = ParseErrorKind::InvalidRowWidth {
+                    expected: width,
+                    found: line.chars().count(),
+                };
+                return Err(ParseError::new(kind, Location::new(y + 1, 1)));
+            }
+
+            width = line.chars().count();
+            for (x, char) in line.chars().enumerate() {
+                let tree_height = char.to_digit(10).ok_or_else(|| {
+                    let kind = ParseErrorKind::InvalidHeight(char);
+                    ParseError::new(kind, Location::new(y + 1, x + 1))
+                })?;
+                trees.push(Tree::new(tree_height as u8));
             }
             height += 1;
         }
@@ -43,7 +81,9 @@ impl Forest {
             height,
         })
     }
+}
 
+impl Forest {
     fn get_tree(&self, x: usize, y: usize) -> Option<&Tree> {
         if y >= self.height {
             return None;
@@ -83,9 +123,48 @@ impl Forest {
             || is_tree_visible_from_right
     }
 
+    fn scenic_score(&self, x: usize, y: usize) -> usize {
+        let height = self.get_tree(x, y).unwrap().height();
+
+        let viewing_distance_to_left = self.viewing_distance(height, (0..x).rev().map(|x| (x, y)));
+        let viewing_distance_to_right =
+            self.viewing_distance(height, (x + 1..self.width).map(|x| (x, y)));
+        let viewing_distance_to_top = self.viewing_distance(height, (0..y).rev().map(|y| (x, y)));
+        let viewing_distance_to_bottom =
+            self.viewing_distance(height, (y + 1..self.height).map(|y| (x, y)));
+
+        viewing_distance_to_left
+            * viewing_distance_to_right
+            * viewing_distance_to_top
+            * viewing_distance_to_bottom
+    }
+
+    fn viewing_distance(
+        &self,
+        height: u8,
+        positions: impl Iterator<Item = (usize, usize)>,
+    ) -> usize {
+        let mut distance = 0;
+        for (x, y) in positions {
+            distance += 1;
+            if self.get_tree(x, y).unwrap().height() >= height {
+                break;
+            }
+        }
+        distance
+    }
+
+    pub fn highest_scenic_score(&self) -> usize {
+        (0..self.width())
+            .cartesian_product(0..self.height())
+            .map(|(x, y)| self.scenic_score(x, y))
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn visible_tree_count(&self) -> usize {
         (0..self.width())
-            .cartesian_product((0..self.height()))
+            .cartesian_product(0..self.height())
             .filter(|(x, y)| self.is_tree_visible(*x, *y))
             .count()
     }
@@ -99,6 +178,27 @@ impl Forest {
     }
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Forest;
+    type Error = ParseError;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Forest::from_str(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(input.visible_tree_count())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(input.highest_scenic_score())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +266,42 @@ mod tests {
         assert_eq!(visible_trees, 21);
     }
 
+    #[test]
+    fn it_calculates_the_scenic_score_of_a_tree() {
+        let forest = Forest::from_str(input()).unwrap();
+
+        assert_eq!(forest.scenic_score(2, 1), 4);
+        assert_eq!(forest.scenic_score(2, 3), 8);
+    }
+
+    #[test]
+    fn it_calculates_the_highest_scenic_score() {
+        let forest = Forest::from_str(input()).unwrap();
+
+        assert_eq!(forest.highest_scenic_score(), 8);
+    }
+
+    #[test]
+    fn it_reports_where_the_forest_is_malformed() {
+        let invalid_height = Forest::from_str("303\n2x5").err().unwrap();
+        let invalid_width = Forest::from_str("303\n25").err().unwrap();
+
+        assert_eq!(
+            invalid_height,
+            ParseError::new(ParseErrorKind::InvalidHeight('x'), Location::new(2, 2))
+        );
+        assert_eq!(
+            invalid_width,
+            ParseError::new(
+                ParseErrorKind::InvalidRowWidth {
+                    expected: 3,
+                    found: 2
+                },
+                Location::new(2, 1)
+            )
+        );
+    }
+
     fn input() -> &'static str {
         "\
 30373
@@ -1,10 +1,53 @@
 #![allow(unused)]
 
+use advent::{Location, Solution, Span};
 use std::collections::HashSet;
-use std::fmt::Display;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingHead,
+    InvalidDirection(String),
+    MissingCount,
+    InvalidCount(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::MissingHead => write!(f, "no head position found"),
+            ParseErrorKind::InvalidDirection(direction) => {
+                write!(f, "invalid direction '{}'", direction)
+            }
+            ParseErrorKind::MissingCount => write!(f, "no count found"),
+            ParseErrorKind::InvalidCount(count) => write!(f, "count '{}' is not a number", count),
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GridError {
+    OutOfBounds(Direction),
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::OutOfBounds(direction) => write!(
+                f,
+                "cannot move head {:?}, it would be out of bounds",
+                direction
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
@@ -43,7 +86,7 @@ impl Display for Grid {
 }
 
 impl Grid {
-    pub fn from_str(input: &str) -> Result<Grid, String> {
+    pub fn from_str(input: &str) -> Result<Grid, ParseError> {
         let mut width = 0;
         let mut height = 0;
         let mut head_position = None;
@@ -61,7 +104,9 @@ impl Grid {
             height += 1;
         }
 
-        let head_position = head_position.ok_or("No head position found")?;
+        let head_position = head_position.ok_or_else(|| {
+            ParseError::new(ParseErrorKind::MissingHead, Location::new(height.max(1), 1))
+        })?;
         let tail_position = tail_position.unwrap_or(head_position);
         Ok(Grid {
             width,
@@ -89,7 +134,7 @@ impl Grid {
         self.tail_position
     }
 
-    pub fn move_head(&mut self, direction: &Direction) -> Result<(), String> {
+    pub fn move_head(&mut self, direction: &Direction) -> Result<(), GridError> {
         self.head_position = self.get_new_head_position(direction)?;
         self.tail_position = self.get_new_tail_position();
         self.tail_movements.insert(self.tail_position);
@@ -97,25 +142,18 @@ impl Grid {
         Ok(())
     }
 
-    fn get_new_head_position(&self, direction: &Direction) -> Result<(usize, usize), String> {
+    fn get_new_head_position(&self, direction: &Direction) -> Result<(usize, usize), GridError> {
+        let out_of_bounds = GridError::OutOfBounds(*direction);
         let (x, y) = self.head_position;
         let (new_x, new_y) = match direction {
-            Direction::Up => (
-                x,
-                y.checked_sub(1)
-                    .ok_or("Cannot move up, would be out of bounds")?,
-            ),
+            Direction::Up => (x, y.checked_sub(1).ok_or(out_of_bounds.clone())?),
             Direction::Down => (x, y + 1),
-            Direction::Left => (
-                x.checked_sub(1)
-                    .ok_or("Cannot move left, would be out of bounds")?,
-                y,
-            ),
+            Direction::Left => (x.checked_sub(1).ok_or(out_of_bounds.clone())?, y),
             Direction::Right => (x + 1, y),
         };
 
         if new_x >= self.width || new_y >= self.height {
-            return Err("cannot move head there, it would be out of bounds".to_string());
+            return Err(out_of_bounds);
         }
 
         Ok((new_x, new_y))
@@ -163,31 +201,87 @@ impl Grid {
     }
 }
 
-fn instructions_from_str(input: &str) -> Result<Vec<Direction>, String> {
+pub struct Rope {
+    knots: Vec<(i32, i32)>,
+    tail_movements: HashSet<(i32, i32)>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize) -> Rope {
+        Rope {
+            knots: vec![(0, 0); knot_count.max(1)],
+            tail_movements: HashSet::from([(0, 0)]),
+        }
+    }
+
+    pub fn move_head(&mut self, direction: &Direction) {
+        let head = &mut self.knots[0];
+        match direction {
+            Direction::Up => head.1 -= 1,
+            Direction::Down => head.1 += 1,
+            Direction::Left => head.0 -= 1,
+            Direction::Right => head.0 += 1,
+        }
+
+        for i in 1..self.knots.len() {
+            let (x_leader, y_leader) = self.knots[i - 1];
+            let (x_follower, y_follower) = self.knots[i];
+
+            let x_diff = x_leader - x_follower;
+            let y_diff = y_leader - y_follower;
+            if x_diff.abs() < 2 && y_diff.abs() < 2 {
+                break;
+            }
+
+            self.knots[i] = (x_follower + x_diff.signum(), y_follower + y_diff.signum());
+        }
+
+        if let Some(tail) = self.knots.last() {
+            self.tail_movements.insert(*tail);
+        }
+    }
+
+    pub fn unique_tail_movements(&self) -> usize {
+        self.tail_movements.len()
+    }
+}
+
+pub fn unique_tail_movements(instructions: &[Direction], knot_count: usize) -> usize {
+    let mut rope = Rope::new(knot_count);
+
+    for instruction in instructions {
+        rope.move_head(instruction);
+    }
+
+    rope.unique_tail_movements()
+}
+
+fn instructions_from_str(input: &str) -> Result<Vec<Direction>, ParseError> {
     let mut instructions = Vec::new();
 
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         let mut split = line.trim().split(' ');
-        let direction_char = split
-            .next()
-            .ok_or("No direction found")?
-            .chars()
-            .next()
-            .ok_or("Char could not be extracted from direction")?;
-        let direction_count = split
-            .next()
-            .map(|s| s.parse::<usize>())
-            .ok_or("No count found")?
-            .map_err(|_| "Could not parse count")?;
-
-        let direction = match direction_char {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => return Err("Invalid direction".to_string()),
+        let direction_word = split.next().unwrap_or(line);
+        let direction = match direction_word {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            _ => {
+                let kind = ParseErrorKind::InvalidDirection(direction_word.to_string());
+                return Err(ParseError::new(kind, Span::of(i + 1, line, direction_word)));
+            }
         };
 
+        let count_word = split.next().ok_or_else(|| {
+            let location = Span::of(i + 1, line, &line[line.len()..]);
+            ParseError::new(ParseErrorKind::MissingCount, location)
+        })?;
+        let direction_count = count_word.parse::<usize>().map_err(|_| {
+            let kind = ParseErrorKind::InvalidCount(count_word.to_string());
+            ParseError::new(kind, Span::of(i + 1, line, count_word))
+        })?;
+
         for _ in 0..direction_count {
             instructions.push(direction);
         }
@@ -196,6 +290,27 @@ fn instructions_from_str(input: &str) -> Result<Vec<Direction>, String> {
     Ok(instructions)
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Direction>;
+    type Error = ParseError;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        instructions_from_str(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Ok(unique_tail_movements(input, 2))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Ok(unique_tail_movements(input, 10))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +546,61 @@ s###..";
         assert_eq!(grid.unique_tail_movements(), 13);
     }
 
+    #[test]
+    fn it_returns_the_number_of_tail_movements_of_a_rope_with_two_knots() {
+        let instructions = instructions_from_str(instructions_input()).unwrap();
+
+        let movements = unique_tail_movements(&instructions, 2);
+
+        assert_eq!(movements, 13);
+    }
+
+    #[test]
+    fn it_returns_the_number_of_tail_movements_of_a_rope_with_ten_knots() {
+        let instructions = instructions_from_str(instructions_input()).unwrap();
+        let larger_instructions = instructions_from_str(larger_instructions_input()).unwrap();
+
+        let movements = unique_tail_movements(&instructions, 10);
+        let larger_movements = unique_tail_movements(&larger_instructions, 10);
+
+        assert_eq!(movements, 1);
+        assert_eq!(larger_movements, 36);
+    }
+
+    #[test]
+    fn it_reports_where_an_instruction_is_malformed() {
+        let errors = ["R 4\nX 4", "R 4\nU", "R 4\nU four"]
+            .map(|input| instructions_from_str(input).unwrap_err());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::InvalidDirection("X".to_string()),
+                Location::new(2, 1)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(ParseErrorKind::MissingCount, Location::new(2, 2))
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::InvalidCount("four".to_string()),
+                Span::new(2, 3, 4)
+            )
+        );
+    }
+
+    #[test]
+    fn it_fails_to_move_the_head_out_of_the_grid() {
+        let mut grid = Grid::from_str("H.").unwrap();
+
+        let result = grid.move_head(&Direction::Left);
+
+        assert_eq!(result, Err(GridError::OutOfBounds(Direction::Left)));
+    }
+
     fn grid_input() -> &'static str {
         "\
 ......
@@ -452,4 +622,16 @@ D 1
 L 5
 R 2"
     }
+
+    fn larger_instructions_input() -> &'static str {
+        "\
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20"
+    }
 }
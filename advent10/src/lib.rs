@@ -1,21 +1,88 @@
 #![allow(unused)]
 
+use advent::{Diagnostic, Location, Solution, Span};
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Eq, PartialEq)]
-enum Command {
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnknownCommand(String),
+    MissingArgument,
+    InvalidArgument(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            ParseErrorKind::MissingArgument => write!(f, "addx requires an argument"),
+            ParseErrorKind::InvalidArgument(argument) => {
+                write!(f, "argument '{}' is not a number", argument)
+            }
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    NoMoreCommands { cycle: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::NoMoreCommands { cycle } => {
+                write!(f, "no more commands to run at cycle {}", cycle)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::NoMoreCommands { .. } => None,
+        }
+    }
+}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::Parse(e) => Some(e.span()),
+            Error::NoMoreCommands { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Command {
     Noop,
     Addx(i32),
 }
 
-struct Computer {
+pub struct Computer {
     commands: VecDeque<Command>,
     clock: usize,
     register: i32,
 }
 
 impl Computer {
-    fn new(commands: Vec<Command>) -> Self {
+    pub fn new(commands: Vec<Command>) -> Self {
         let new_commands = Self::extend_commands(commands);
         Self {
             clock: 1,
@@ -41,9 +108,12 @@ impl Computer {
         new_commands
     }
 
-    fn run(&mut self, clock_count: usize) -> Result<i32, String> {
+    pub fn run(&mut self, clock_count: usize) -> Result<i32, Error> {
         for _ in self.clock..clock_count {
-            let command = self.commands.pop_front().ok_or("No more commands to run")?;
+            let command = self
+                .commands
+                .pop_front()
+                .ok_or(Error::NoMoreCommands { cycle: self.clock })?;
             match command {
                 Command::Noop => {}
                 Command::Addx(count) => {
@@ -55,28 +125,87 @@ impl Computer {
 
         Ok(self.register)
     }
+
+    pub fn signal_strength_sum(&mut self) -> Result<i32, Error> {
+        (20..=220)
+            .step_by(40)
+            .map(|cycle| Ok(self.run(cycle)? * cycle as i32))
+            .sum()
+    }
+
+    pub fn render(&mut self) -> Result<String, Error> {
+        let mut screen = String::with_capacity((CRT_WIDTH + 1) * CRT_HEIGHT);
+
+        for cycle in 1..=CRT_WIDTH * CRT_HEIGHT {
+            let sprite_position = self.run(cycle)?;
+            let pixel_position = ((cycle - 1) % CRT_WIDTH) as i32;
+
+            if (sprite_position - pixel_position).abs() <= 1 {
+                screen.push('#');
+            } else {
+                screen.push('.');
+            }
+
+            if cycle % CRT_WIDTH == 0 {
+                screen.push('\n');
+            }
+        }
+
+        Ok(screen.trim_end().to_string())
+    }
 }
 
-fn parse_command(input: &str) -> Option<Command> {
+fn parse_command(input: &str) -> Result<Command, ParseError> {
     let mut words = input.trim().split(' ');
-    let command = words.next()?;
+    let command = words.next().unwrap_or(input);
     match command {
         "addx" => {
-            let count = words.next()?.parse::<i32>().ok()?;
-            Some(Command::Addx(count))
+            let count = words.next().ok_or_else(|| {
+                let location = Span::of(1, input, &input[input.len()..]);
+                ParseError::new(ParseErrorKind::MissingArgument, location)
+            })?;
+            let count = count.parse::<i32>().map_err(|_| {
+                let kind = ParseErrorKind::InvalidArgument(count.to_string());
+                ParseError::new(kind, Span::of(1, input, count))
+            })?;
+            Ok(Command::Addx(count))
+        }
+        "noop" => Ok(Command::Noop),
+        _ => {
+            let kind = ParseErrorKind::UnknownCommand(command.to_string());
+            Err(ParseError::new(kind, Span::of(1, input, command)))
         }
-        "noop" => Some(Command::Noop),
-        _ => None,
     }
 }
 
-fn parse_program(input: &str) -> Result<Vec<Command>, String> {
+pub fn parse_program(input: &str) -> Result<Vec<Command>, ParseError> {
     input
-        .trim()
         .lines()
-        .map(parse_command)
-        .collect::<Option<Vec<_>>>()
-        .ok_or_else(|| "Invalid input".to_string())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_command(line).map_err(|e| e.with_line_offset(i)))
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Command>;
+    type Error = Error;
+    type PartOne = i32;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        Computer::new(input.clone()).signal_strength_sum()
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        Computer::new(input.clone()).render()
+    }
 }
 
 #[cfg(test)]
@@ -149,6 +278,61 @@ mod tests {
         assert_eq!(signal_sum, 13140);
     }
 
+    #[test]
+    fn it_calculates_the_signal_strength_sum() {
+        let commands = parse_program(input()).unwrap();
+        let mut computer = Computer::new(commands);
+
+        assert_eq!(computer.signal_strength_sum().unwrap(), 13140);
+    }
+
+    #[test]
+    fn it_renders_the_image_in_the_crt() {
+        let commands = parse_program(input()).unwrap();
+        let mut computer = Computer::new(commands);
+
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(computer.render().unwrap(), expected);
+    }
+
+    #[test]
+    fn it_reports_where_the_program_is_malformed() {
+        let errors = ["noop\nmulx 3", "noop\naddx", "noop\naddx three"]
+            .map(|input| parse_program(input).unwrap_err());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::UnknownCommand("mulx".to_string()),
+                Span::new(2, 1, 4)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(ParseErrorKind::MissingArgument, Location::new(2, 5))
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::InvalidArgument("three".to_string()),
+                Span::new(2, 6, 5)
+            )
+        );
+    }
+
+    #[test]
+    fn it_fails_when_the_program_ends_before_the_cycle() {
+        let mut computer = Computer::new(parse_program("noop\naddx 3").unwrap());
+
+        assert_eq!(computer.run(5), Err(Error::NoMoreCommands { cycle: 4 }));
+    }
+
     fn input() -> &'static str {
         "\
 addx 15
@@ -2,41 +2,143 @@
 
 mod monkey;
 
+use advent::{Diagnostic, Solution, Span};
 use itertools::Itertools;
 use monkey::Monkey;
+use std::fmt::{Display, Formatter};
 
-struct RoundExecutor {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingLine(&'static str),
+    UnexpectedLine(&'static str),
+    InvalidItem(String),
+    InvalidOperand(String),
+    UnknownOperator(String),
+    InvalidDivisor(String),
+    InvalidMonkey(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::MissingLine(expected) => {
+                write!(f, "missing line starting with '{}'", expected.trim())
+            }
+            ParseErrorKind::UnexpectedLine(expected) => {
+                write!(f, "expected a line like '{}'", expected.trim())
+            }
+            ParseErrorKind::InvalidItem(item) => write!(f, "item '{}' is not a number", item),
+            ParseErrorKind::InvalidOperand(operand) => {
+                write!(f, "operand '{}' is not a number nor 'old'", operand)
+            }
+            ParseErrorKind::UnknownOperator(operator) => {
+                write!(f, "unknown operator '{}'", operator)
+            }
+            ParseErrorKind::InvalidDivisor(divisor) => {
+                write!(f, "divisor '{}' is not a positive number", divisor)
+            }
+            ParseErrorKind::InvalidMonkey(monkey) => {
+                write!(f, "monkey '{}' is not a number", monkey)
+            }
+        }
+    }
+}
+
+pub type ParseError = advent::ParseError<ParseErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    NoSuchMonkey(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::NoSuchMonkey(monkey) => write!(f, "there is no monkey {}", monkey),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::NoSuchMonkey(_) => None,
+        }
+    }
+}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::Parse(e) => Some(e.span()),
+            Error::NoSuchMonkey(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+fn monkeys_from_str(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    input
+        .trim_end()
+        .split("\n\n")
+        .map(|block| {
+            let block_start = block.as_ptr() as usize - input.as_ptr() as usize;
+            let line_offset = input[..block_start].matches('\n').count();
+            Monkey::try_from(block).map_err(|e| e.with_line_offset(line_offset))
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct RoundExecutor {
     monkeys: Vec<Monkey>,
     round: usize,
 }
 
 impl TryFrom<&str> for RoundExecutor {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        let monkeys = input
-            .trim()
-            .split("\n\n")
-            .map(TryFrom::try_from)
-            .collect::<Result<Vec<Monkey>, String>>()?;
+        let monkeys = monkeys_from_str(input)?;
 
         Ok(Self { monkeys, round: 0 })
     }
 }
 
 impl RoundExecutor {
-    fn execute_round(&mut self) -> Result<(), String> {
+    pub fn execute_round(&mut self) -> Result<(), Error> {
+        self.execute_round_with(Monkey::evaluate_object)
+    }
+
+    pub fn execute_round_without_relief(&mut self) -> Result<(), Error> {
+        let worry_modulus = self
+            .monkeys
+            .iter()
+            .map(Monkey::test_divisor)
+            .product::<i64>();
+        self.execute_round_with(|monkey| monkey.evaluate_object_without_relief(worry_modulus))
+    }
+
+    fn execute_round_with(&mut self, evaluate: impl Fn(&mut Monkey) -> i64) -> Result<(), Error> {
         for i in 0..self.monkeys.len() {
             let monkey_items = self
                 .monkeys
                 .get(i)
-                .ok_or("no monkey with this index")?
+                .ok_or(Error::NoSuchMonkey(i))?
                 .items()
                 .len();
 
             for _ in 0..monkey_items {
-                let monkey = self.monkeys.get_mut(i).ok_or("no monkey with this index")?;
-                let item = monkey.evaluate_object();
+                let monkey = self.monkeys.get_mut(i).ok_or(Error::NoSuchMonkey(i))?;
+                let item = evaluate(monkey);
                 let monkey_to_throw = if monkey.does_test_pass(item) {
                     monkey.monkey_to_throw_if_passes()
                 } else {
@@ -45,7 +147,7 @@ impl RoundExecutor {
 
                 self.monkeys
                     .get_mut(monkey_to_throw)
-                    .ok_or("no money with this index")?
+                    .ok_or(Error::NoSuchMonkey(monkey_to_throw))?
                     .receive_item(item);
             }
         }
@@ -57,7 +159,7 @@ impl RoundExecutor {
         &self.monkeys
     }
 
-    fn monkey_business(&self) -> i64 {
+    pub fn monkey_business(&self) -> i64 {
         self.monkeys
             .iter()
             .map(|monkey| monkey.evaluations() as i64)
@@ -68,6 +170,35 @@ impl RoundExecutor {
     }
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = RoundExecutor;
+    type Error = Error;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(RoundExecutor::try_from(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Self::Error> {
+        let mut round_executor = input.clone();
+        for _ in 0..20 {
+            round_executor.execute_round()?;
+        }
+        Ok(round_executor.monkey_business())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Self::Error> {
+        let mut round_executor = input.clone();
+        for _ in 0..10000 {
+            round_executor.execute_round_without_relief()?;
+        }
+        Ok(round_executor.monkey_business())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,6 +266,37 @@ mod tests {
         assert_eq!(round_executor.monkey_business(), 10605);
     }
 
+    #[test]
+    fn it_calculates_the_monkey_business_without_relief() {
+        let mut round_executor = RoundExecutor::try_from(input()).unwrap();
+
+        for _ in 0..10000 {
+            round_executor.execute_round_without_relief().unwrap();
+        }
+
+        assert_eq!(round_executor.monkeys()[0].evaluations(), 52166);
+        assert_eq!(round_executor.monkey_business(), 2713310158);
+    }
+
+    #[test]
+    fn it_reports_the_line_of_the_malformed_monkey_in_the_input() {
+        let input = input().replacen("throw to monkey 1", "throw to monkey one", 1);
+
+        let error = RoundExecutor::try_from(input.as_str()).err().unwrap();
+
+        assert_eq!(error.location(), advent::Location::new(19, 30));
+    }
+
+    #[test]
+    fn it_fails_to_throw_to_a_monkey_that_does_not_exist() {
+        let input = input().replacen("throw to monkey 3", "throw to monkey 7", 1);
+        let mut round_executor = RoundExecutor::try_from(input.as_str()).unwrap();
+
+        let result = round_executor.execute_round();
+
+        assert_eq!(result, Err(Error::NoSuchMonkey(7)));
+    }
+
     fn input() -> &'static str {
         include_str!("example.txt")
     }
@@ -1,61 +1,103 @@
+use crate::{ParseError, ParseErrorKind};
+use advent::{Location, Span};
 use regex::Regex;
 use std::collections::VecDeque;
 
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Old,
+    Value(i64),
+}
+
+impl Operand {
+    fn value(&self, old: i64) -> i64 {
+        match self {
+            Operand::Old => old,
+            Operand::Value(value) => *value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+}
+
+impl Operation {
+    fn apply(&self, old: i64) -> i64 {
+        let first_operand = self.left.value(old);
+        let second_operand = self.right.value(old);
+
+        match self.operator {
+            Operator::Add => first_operand + second_operand,
+            Operator::Subtract => first_operand - second_operand,
+            Operator::Multiply => first_operand * second_operand,
+            Operator::Divide => first_operand.checked_div(second_operand).unwrap(),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Monkey {
     items: VecDeque<i64>,
-    worry_fn: Box<dyn Fn(i64) -> i64>,
-    test: Box<dyn Fn(i64) -> bool>,
+    operation: Operation,
+    test_divisor: i64,
     monkey_to_throw_if_true: usize,
     monkey_to_throw_if_false: usize,
     evaluations: usize,
 }
 
 impl TryFrom<&str> for Monkey {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        let regex = Regex::new(
-            r#"Monkey \d+:
-  Starting items: ([\d,\s]+)
-  Operation: new = (\w+) ([+\-*/]) (\w+)
-  Test: divisible by (\d+)
-    If true: throw to monkey (\d+)
-    If false: throw to monkey (\d+)"#,
-        )
-        .map_err(|e| format!("failed to create regex: {}", e))?;
-        let captures = regex.captures(input).ok_or("input does not match regex")?;
-
-        let starting_items_line = captures.get(1).ok_or("no starting items found")?.as_str();
-        let starting_items = starting_items_from_str(starting_items_line)?;
-
-        let left_operand = captures.get(2).ok_or("no left operand found")?.as_str();
-        let operator = captures.get(3).ok_or("no operator found")?.as_str();
-        let right_operand = captures.get(4).ok_or("no right operand found")?.as_str();
-        let operation = operation_from_str(left_operand, operator, right_operand)?;
-
-        let test_divisor = captures.get(5).ok_or("no test divisor found")?.as_str();
-        let test = test_from_str(test_divisor)?;
-
-        let monkey_to_throw_if_true = captures
-            .get(6)
-            .ok_or("no monkey to throw if true found")?
-            .as_str()
-            .trim()
-            .parse::<usize>()
-            .map_err(|_| "unable to parse monkey to throw to")?;
-
-        let monkey_to_throw_if_false = captures
-            .get(7)
-            .ok_or("no monkey to throw if false found")?
-            .as_str()
-            .trim()
-            .parse::<usize>()
-            .map_err(|_| "unable to parse monkey to throw to")?;
+        let mut lines = input.lines().enumerate();
+        let mut next_line = |prefix: &'static str, pattern: &str| {
+            let (i, line) = lines.next().ok_or_else(|| {
+                let location = Location::new(input.lines().count() + 1, 1);
+                ParseError::new(ParseErrorKind::MissingLine(prefix), location)
+            })?;
+            line_captures(line, prefix, pattern)
+                .map(|(line, captures)| (i, line, captures))
+                .map_err(|e| e.with_line_offset(i))
+        };
+
+        let _ = next_line("Monkey ", r"^(\d+):$")?;
+
+        let (i, line, captures) = next_line("  Starting items: ", r"^(.*)$")?;
+        let starting_items =
+            starting_items_from_str(line, captures[0]).map_err(|e| e.with_line_offset(i))?;
+
+        let (i, line, captures) = next_line("  Operation: new = ", r"^(\w+) (\S+) (\w+)$")?;
+        let operation = operation_from_str(line, captures[0], captures[1], captures[2])
+            .map_err(|e| e.with_line_offset(i))?;
+
+        let (i, line, captures) = next_line("  Test: divisible by ", r"^(\S+)$")?;
+        let test_divisor =
+            test_divisor_from_str(line, captures[0]).map_err(|e| e.with_line_offset(i))?;
+
+        let (i, line, captures) = next_line("    If true: throw to monkey ", r"^(\S+)$")?;
+        let monkey_to_throw_if_true =
+            monkey_from_str(line, captures[0]).map_err(|e| e.with_line_offset(i))?;
+
+        let (i, line, captures) = next_line("    If false: throw to monkey ", r"^(\S+)$")?;
+        let monkey_to_throw_if_false =
+            monkey_from_str(line, captures[0]).map_err(|e| e.with_line_offset(i))?;
 
         Ok(Self {
             items: VecDeque::from(starting_items),
-            worry_fn: Box::new(operation),
-            test: Box::new(test),
+            operation,
+            test_divisor,
             monkey_to_throw_if_true,
             monkey_to_throw_if_false,
             evaluations: 0,
@@ -69,11 +111,15 @@ impl Monkey {
     }
 
     fn worry(&self, item: i64) -> i64 {
-        (self.worry_fn)(item)
+        self.operation.apply(item)
     }
 
     pub fn does_test_pass(&self, item: i64) -> bool {
-        (self.test)(item)
+        item % self.test_divisor == 0
+    }
+
+    pub fn test_divisor(&self) -> i64 {
+        self.test_divisor
     }
 
     pub fn monkey_to_throw_if_passes(&self) -> usize {
@@ -92,6 +138,12 @@ impl Monkey {
         item_is_not_broken_so_less_worry.floor() as i64
     }
 
+    pub fn evaluate_object_without_relief(&mut self, worry_modulus: i64) -> i64 {
+        self.evaluations += 1;
+        let item = self.items.pop_front().unwrap();
+        self.worry(item) % worry_modulus
+    }
+
     pub(crate) fn evaluations(&self) -> usize {
         self.evaluations
     }
@@ -101,64 +153,109 @@ impl Monkey {
     }
 }
 
-fn parse_monkeys_from_str(input: &str) -> Result<Vec<Monkey>, String> {
-    input
-        .trim()
-        .split("\n\n")
-        .map(Monkey::try_from)
-        .collect::<Result<Vec<Monkey>, String>>()
+/// Checks that the line starts with `prefix` and the rest of it matches `pattern`, returning
+/// the line and the captured groups of the pattern.
+fn line_captures<'a>(
+    line: &'a str,
+    prefix: &'static str,
+    pattern: &str,
+) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+    let unexpected_line = |token: &str| {
+        let kind = ParseErrorKind::UnexpectedLine(prefix);
+        ParseError::new(kind, Span::of(1, line, token))
+    };
+
+    let rest = line.strip_prefix(prefix).ok_or_else(|| {
+        let matching = line
+            .char_indices()
+            .zip(prefix.chars())
+            .take_while(|((_, a), b)| a == b)
+            .count();
+        let mismatch = line
+            .char_indices()
+            .nth(matching)
+            .map_or(line.len(), |(i, _)| i);
+        unexpected_line(&line[mismatch..])
+    })?;
+
+    let regex = Regex::new(pattern).expect("line patterns are valid regexes");
+    let captures = regex.captures(rest).ok_or_else(|| unexpected_line(rest))?;
+    let groups = captures
+        .iter()
+        .skip(1)
+        .flatten()
+        .map(|group| group.as_str())
+        .collect();
+
+    Ok((line, groups))
 }
 
-fn starting_items_from_str(starting_items_line: &str) -> Result<Vec<i64>, String> {
-    let starting_items = starting_items_line
-        .trim()
+fn starting_items_from_str(line: &str, starting_items: &str) -> Result<Vec<i64>, ParseError> {
+    starting_items
         .split(',')
-        .filter_map(|item| item.trim().parse::<i64>().ok())
-        .collect::<Vec<_>>();
-
-    Ok(starting_items)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.parse::<i64>().map_err(|_| {
+                let kind = ParseErrorKind::InvalidItem(item.to_string());
+                ParseError::new(kind, Span::of(1, line, item))
+            })
+        })
+        .collect()
 }
 
 fn operation_from_str(
+    line: &str,
     left_operand: &str,
     operator: &str,
     right_operand: &str,
-) -> Result<impl Fn(i64) -> i64, String> {
-    let left_operand = left_operand.to_string();
-    let operator = operator.to_string();
-    let right_operand = right_operand.to_string();
-
-    let func = move |old: i64| -> i64 {
-        let first_operand = match left_operand.as_str() {
-            "old" => old,
-            _ => left_operand.parse::<i64>().unwrap(),
-        };
-
-        let second_operand = match right_operand.as_str() {
-            "old" => old,
-            _ => right_operand.parse::<i64>().unwrap(),
-        };
-
-        match operator.as_str() {
-            "+" => first_operand + second_operand,
-            "-" => first_operand - second_operand,
-            "*" => first_operand * second_operand,
-            "/" => first_operand.checked_div(second_operand).unwrap(),
-            _ => 0,
+) -> Result<Operation, ParseError> {
+    let left = operand_from_str(line, left_operand)?;
+    let right = operand_from_str(line, right_operand)?;
+    let operator = match operator {
+        "+" => Operator::Add,
+        "-" => Operator::Subtract,
+        "*" => Operator::Multiply,
+        "/" => Operator::Divide,
+        _ => {
+            let kind = ParseErrorKind::UnknownOperator(operator.to_string());
+            return Err(ParseError::new(kind, Span::of(1, line, operator)));
         }
     };
 
-    Ok(func)
+    Ok(Operation {
+        left,
+        operator,
+        right,
+    })
 }
 
-fn test_from_str(input: &str) -> Result<impl Fn(i64) -> bool, String> {
-    let num_to_divide = input
-        .parse::<i64>()
-        .map_err(|e| format!("failed to parse divisor: {} for input: {}", e, input))?;
+fn operand_from_str(line: &str, input: &str) -> Result<Operand, ParseError> {
+    match input {
+        "old" => Ok(Operand::Old),
+        _ => input.parse::<i64>().map(Operand::Value).map_err(|_| {
+            let kind = ParseErrorKind::InvalidOperand(input.to_string());
+            ParseError::new(kind, Span::of(1, line, input))
+        }),
+    }
+}
 
-    let func = move |num: i64| -> bool { num % num_to_divide == 0 };
+fn test_divisor_from_str(line: &str, input: &str) -> Result<i64, ParseError> {
+    input
+        .parse::<i64>()
+        .ok()
+        .filter(|divisor| *divisor > 0)
+        .ok_or_else(|| {
+            let kind = ParseErrorKind::InvalidDivisor(input.to_string());
+            ParseError::new(kind, Span::of(1, line, input))
+        })
+}
 
-    Ok(func)
+fn monkey_from_str(line: &str, input: &str) -> Result<usize, ParseError> {
+    input.parse::<usize>().map_err(|_| {
+        let kind = ParseErrorKind::InvalidMonkey(input.to_string());
+        ParseError::new(kind, Span::of(1, line, input))
+    })
 }
 
 #[cfg(test)]
@@ -167,7 +264,7 @@ mod tests {
 
     #[test]
     fn it_loads_the_input_and_creates_the_monkeys() {
-        let monkeys = parse_monkeys_from_str(input()).unwrap();
+        let monkeys = crate::monkeys_from_str(input()).unwrap();
 
         assert_eq!(monkeys.len(), 4);
         assert_eq!(monkeys[0].items(), &Vec::from([79, 98]));
@@ -201,7 +298,7 @@ mod tests {
 
     #[test]
     fn monkey_0_evaluates_an_object_and_sends_it_to_monkey_3() {
-        let mut monkeys = parse_monkeys_from_str(input()).unwrap();
+        let mut monkeys = crate::monkeys_from_str(input()).unwrap();
 
         let monkey_0 = &mut monkeys[0];
         assert_eq!(monkey_0.evaluate_object(), 500);
@@ -217,6 +314,69 @@ mod tests {
         monkey_3.receive_item(500);
     }
 
+    #[test]
+    fn it_reports_where_the_monkey_is_malformed() {
+        let errors = [
+            input().replacen("Starting items", "Startng items", 1),
+            input().replacen("79, 98", "79, x8", 1),
+            input().replacen("old * 19", "old ^ 19", 1),
+            input().replacen("divisible by 23", "divisible by 0", 1),
+            input().replacen("throw to monkey 3", "throw to monkey three", 1),
+        ]
+        .map(|input| Monkey::try_from(input.as_str()).err().unwrap());
+
+        assert_eq!(
+            errors[0],
+            ParseError::new(
+                ParseErrorKind::UnexpectedLine("  Starting items: "),
+                Span::new(2, 8, 16)
+            )
+        );
+        assert_eq!(
+            errors[1],
+            ParseError::new(
+                ParseErrorKind::InvalidItem("x8".to_string()),
+                Span::new(2, 23, 2)
+            )
+        );
+        assert_eq!(
+            errors[2],
+            ParseError::new(
+                ParseErrorKind::UnknownOperator("^".to_string()),
+                Location::new(3, 24)
+            )
+        );
+        assert_eq!(
+            errors[3],
+            ParseError::new(
+                ParseErrorKind::InvalidDivisor("0".to_string()),
+                Location::new(4, 22)
+            )
+        );
+        assert_eq!(
+            errors[4],
+            ParseError::new(
+                ParseErrorKind::InvalidMonkey("three".to_string()),
+                Span::new(6, 31, 5)
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_a_missing_line() {
+        let input = "Monkey 0:\n  Starting items: 79, 98";
+
+        let error = Monkey::try_from(input).err().unwrap();
+
+        assert_eq!(
+            error,
+            ParseError::new(
+                ParseErrorKind::MissingLine("  Operation: new = "),
+                Location::new(3, 1)
+            )
+        );
+    }
+
     fn input() -> &'static str {
         include_str!("example.txt")
     }
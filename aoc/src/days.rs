@@ -0,0 +1,52 @@
+use advent::{diagnostic, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<&str> for Part {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Part '{}' must be 1 or 2", value)),
+        }
+    }
+}
+
+pub const DAYS: u8 = 11;
+
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String, String> {
+    match day {
+        1 => solve_with::<advent01::Day01>(part, input),
+        2 => solve_with::<advent02::Day02>(part, input),
+        3 => solve_with::<advent03::Day03>(part, input),
+        4 => solve_with::<advent04::Day04>(part, input),
+        5 => solve_with::<advent05::Day05>(part, input),
+        6 => solve_with::<advent06::Day06>(part, input),
+        7 => solve_with::<advent07::Day07>(part, input),
+        8 => solve_with::<advent08::Day08>(part, input),
+        9 => solve_with::<advent09::Day09>(part, input),
+        10 => solve_with::<advent10::Day10>(part, input),
+        11 => solve_with::<advent11::Day11>(part, input),
+        _ => Err(format!(
+            "Day {} is not solved, choose one from 1 to {}",
+            day, DAYS
+        )),
+    }
+}
+
+/// Errors are rendered with the snippet of `source` they point to, if any.
+fn solve_with<S: Solution>(part: Part, source: &str) -> Result<String, String> {
+    let input = S::parse(source).map_err(|e| diagnostic::render(source, &e))?;
+
+    let answer = match part {
+        Part::One => S::part_one(&input).map(|answer| answer.to_string()),
+        Part::Two => S::part_two(&input).map(|answer| answer.to_string()),
+    };
+    answer.map_err(|e| diagnostic::render(source, &e))
+}
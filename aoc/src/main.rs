@@ -0,0 +1,181 @@
+mod days;
+
+use days::Part;
+use std::io::Read;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: aoc run <day> [--part <1|2>] [--input <path>]
+
+Runs the solution of the given day against the input file, or against stdin when
+no input path (or '-') is given. Both parts are run unless --part is given.";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day: u8,
+    part: Option<Part>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    match args.next() {
+        Some("run") => {}
+        Some(command) => return Err(format!("Unknown command '{}'", command)),
+        None => return Err("No command given".to_string()),
+    }
+
+    let day = args.next().ok_or("No day given")?;
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Day '{}' is not a number", day))?;
+
+    let mut part = None;
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                part = Some(Part::try_from(value)?);
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("--input requires a path")?;
+                input = Some(value.to_string()).filter(|path| path != "-");
+            }
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(RunArgs { day, part, input })
+}
+
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read input file '{}': {}", path, e)),
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Unable to read input from stdin: {}", e))?;
+            Ok(input)
+        }
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let input = read_input(args.input.as_deref())?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    for part in parts {
+        let answer = days::solve(args.day, part, &input)?;
+        let number = match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+
+        if answer.contains('\n') {
+            println!("Part {}:\n{}", number, answer);
+        } else {
+            println!("Part {}: {}", number, answer);
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let run_args = match parse_args(&args) {
+        Ok(run_args) => run_args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(run_args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn it_parses_the_day_to_run() {
+        let run_args = parse_args(&args("run 5")).unwrap();
+
+        assert_eq!(
+            run_args,
+            RunArgs {
+                day: 5,
+                part: None,
+                input: None
+            }
+        );
+    }
+
+    #[test]
+    fn it_parses_the_part_and_the_input_path() {
+        let run_args = parse_args(&args("run 5 --part 2 --input path/to/input.txt")).unwrap();
+
+        assert_eq!(
+            run_args,
+            RunArgs {
+                day: 5,
+                part: Some(Part::Two),
+                input: Some("path/to/input.txt".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn it_reads_from_stdin_when_the_input_is_a_dash() {
+        let run_args = parse_args(&args("run 1 -i -")).unwrap();
+
+        assert_eq!(run_args.input, None);
+    }
+
+    #[test]
+    fn it_rejects_invalid_arguments() {
+        assert!(parse_args(&args("walk 1")).is_err());
+        assert!(parse_args(&args("run one")).is_err());
+        assert!(parse_args(&args("run 1 --part 3")).is_err());
+        assert!(parse_args(&args("run 1 --input")).is_err());
+        assert!(parse_args(&args("run 1 --verbose")).is_err());
+    }
+
+    #[test]
+    fn it_solves_both_parts_of_a_day() {
+        let input = "A Y\nB X\nC Z\n";
+
+        assert_eq!(days::solve(2, Part::One, input).unwrap(), "15");
+        assert_eq!(days::solve(2, Part::Two, input).unwrap(), "12");
+        assert!(days::solve(42, Part::One, input).is_err());
+    }
+
+    #[test]
+    fn it_shows_where_the_input_is_malformed() {
+        let input = "noop\naddx three\n";
+
+        assert_eq!(
+            days::solve(10, Part::One, input).unwrap_err(),
+            "argument 'three' is not a number at line 2, column 6\n  |\n2 | addx three\n  |      ^^^^^"
+        );
+    }
+}